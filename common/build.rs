@@ -68,6 +68,7 @@ fn main() {
     write_resource("BASE_AUTHORITY_RESOURCE", "_BASE_AUTHORITY_RESOURCE", &decoder, &mut f);
     write_resource("BASE_RESOURCE", "_BASE_RESOURCE", &decoder, &mut f);
     write_resource("LP_RESOURCE", "_LP_RESOURCE", &decoder, &mut f);
+    write_resource("JUNIOR_LP_RESOURCE", "_JUNIOR_LP_RESOURCE", &decoder, &mut f);
     write_resource("REFERRAL_RESOURCE", "_REFERRAL_RESOURCE", &decoder, &mut f);
     write_resource("RECOVERY_KEY_RESOURCE", "_RECOVERY_KEY_RESOURCE", &decoder, &mut f);
     write_resource("PROTOCOL_RESOURCE", "_PROTOCOL_RESOURCE", &decoder, &mut f);
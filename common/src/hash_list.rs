@@ -6,17 +6,20 @@ pub use crate::list::ListIndex;
 pub struct HashList<K: ScryptoSbor + Clone, V: ScryptoSbor + Clone> {
     list: List<K>,
     kvs: KeyValueStore<K, V>,
+    indexes: KeyValueStore<K, ListIndex>,
 }
 
 impl<K: ScryptoSbor + Clone, V: ScryptoSbor + Clone> HashList<K, V> {
-    pub fn new<Fa, Fb>(create_fn0: Fa, create_fn1: Fb) -> Self 
+    pub fn new<Fa, Fb, Fc>(create_fn0: Fa, create_fn1: Fb, create_fn2: Fc) -> Self 
     where
         Fa: Fn() -> KeyValueStore<K, V>,
         Fb: Fn() -> KeyValueStore<ListIndex, K>,
+        Fc: Fn() -> KeyValueStore<K, ListIndex>,
     {
         Self { 
             kvs: create_fn0(),
             list: List::new(create_fn1),
+            indexes: create_fn2(),
         }
     }
 
@@ -26,11 +29,24 @@ impl<K: ScryptoSbor + Clone, V: ScryptoSbor + Clone> HashList<K, V> {
                 *entry = value.to_owned();
             })
             .unwrap_or_else(|| {
+                self.indexes.insert(key.clone(), self.list.len());
                 self.list.push(key.clone());
                 self.kvs.insert(key, value);
             });
     }
 
+    /// Removes the key by moving the last key into its slot, so the list stays dense but its order changes
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.kvs.remove(key)?;
+        let index = self.indexes.remove(key).unwrap();
+        let last = self.list.pop().unwrap();
+        if index < self.list.len() {
+            self.list.update(index, last.clone());
+            self.indexes.insert(last, index);
+        }
+        Some(value)
+    }
+
     pub fn get(&self, key: &K) -> Option<KeyValueEntryRef<V>> {
         self.kvs.get(key)
     }
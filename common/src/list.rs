@@ -33,6 +33,14 @@ impl<T: ScryptoSbor + Clone> List<T> {
         self.kvs.get_mut(&index)
     }
 
+    pub fn pop(&mut self) -> Option<T> {
+        if self.pointer == 0 {
+            return None;
+        }
+        self.pointer -= 1;
+        self.kvs.remove(&self.pointer)
+    }
+
     pub fn update(&mut self, index: ListIndex, item: T) {
         assert!(index < self.pointer, "Index out of bounds");
         self.kvs.insert(index, item);
@@ -1,7 +1,9 @@
+pub mod errors;
 pub mod structs;
 
 use scrypto::prelude::*;
 use common::{PairId, ListIndex, HashList, _AUTHORITY_RESOURCE};
+pub use self::errors::*;
 pub use self::structs::*;
 
 #[blueprint]
@@ -9,9 +11,12 @@ pub use self::structs::*;
     PairId,
     ListIndex,
     PairConfigCompressed,
+    u64,
+    PendingConfigUpdate,
 )]
 mod config_mod {
     const AUTHORITY_RESOURCE: ResourceAddress = _AUTHORITY_RESOURCE;
+    const PENDING_UPDATES_MAX: usize = 32;
 
     enable_method_auth! { 
         roles {
@@ -23,12 +28,12 @@ mod config_mod {
             get_pair_configs => PUBLIC;
             get_pair_configs_by_ids => PUBLIC;
             get_pair_configs_len => PUBLIC;
+            get_pending_updates => PUBLIC;
 
             // Authority protected methods
-            update_exchange_config => restrict_to: [authority];
-            update_pair_configs => restrict_to: [authority];
-            update_collateral_configs => restrict_to: [authority];
-            remove_collateral_config => restrict_to: [authority];
+            schedule_update => restrict_to: [authority];
+            apply_updates => restrict_to: [authority];
+            cancel_update => restrict_to: [authority];
         }
     }
 
@@ -36,18 +41,25 @@ mod config_mod {
         pub exchange: ExchangeConfigCompressed,
         pub pairs: HashList<PairId, PairConfigCompressed>,
         pub collaterals: HashMap<ResourceAddress, CollateralConfigCompressed>,
+        pub pending_updates: KeyValueStore<u64, PendingConfigUpdate>,
+        pub pending_update_ids: Vec<u64>,
+        pub pending_update_next: u64,
     }
 
     impl Config {
         pub fn new(owner_role: OwnerRole) -> Global<Config> {
             let exchange = ExchangeConfig::default().compress();
-            let pairs = HashList::new(ConfigKeyValueStore::new_with_registered_type, ConfigKeyValueStore::new_with_registered_type);
+            let pairs = HashList::new(ConfigKeyValueStore::new_with_registered_type, ConfigKeyValueStore::new_with_registered_type, ConfigKeyValueStore::new_with_registered_type);
             let collaterals = HashMap::new();
+            let pending_updates = KeyValueStore::new_with_registered_type();
 
             Self {
                 exchange,
                 pairs,
                 collaterals,
+                pending_updates,
+                pending_update_ids: vec![],
+                pending_update_next: 0,
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
@@ -86,27 +98,82 @@ mod config_mod {
             self.pairs.len()
         }
 
-        pub fn update_exchange_config(&mut self, config: ExchangeConfig) {
-            config.validate();
-            self.exchange = config.compress();
+        pub fn get_pending_updates(&self) -> Vec<(u64, PendingConfigUpdate)> {
+            self.pending_update_ids.iter().map(|id| {
+                let pending_update = self.pending_updates.get(id).expect(ERROR_MISSING_CONFIG_UPDATE).clone();
+                (*id, pending_update)
+            }).collect()
         }
 
-        pub fn update_pair_configs(&mut self, configs: Vec<PairConfig>) {
-            for config in configs.into_iter() {
-                config.validate();
-                self.pairs.insert(config.pair_id.to_owned(), config.compress());
-            }
+        pub fn schedule_update(&mut self, update: ConfigUpdate) -> (u64, Instant) {
+            update.validate();
+            assert!(
+                self.pending_update_ids.len() < PENDING_UPDATES_MAX,
+                "{}, VALUE:{}, REQUIRED:{}, OP:< |", ERROR_PENDING_CONFIG_UPDATES_FULL, self.pending_update_ids.len(), PENDING_UPDATES_MAX
+            );
+
+            let delay = match &update {
+                ConfigUpdate::Pairs(configs) if configs.iter().all(|config| {
+                    self.pairs.get(&config.pair_id).map_or(false, |current| config.is_restriction_of(&current))
+                }) => 0,
+                _ => update.delay(&self.exchange.decompress()),
+            };
+            let id = self.pending_update_next;
+            let activation = Clock::current_time_rounded_to_seconds().add_seconds(delay).expect(ERROR_ARITHMETIC);
+            self.pending_updates.insert(id, PendingConfigUpdate {
+                update,
+                activation,
+            });
+            self.pending_update_ids.push(id);
+            self.pending_update_next += 1;
+
+            (id, activation)
         }
 
-        pub fn update_collateral_configs(&mut self, configs: Vec<(ResourceAddress, CollateralConfig)>) {
-            for (_, config) in configs.iter() {
-                config.validate();
-            }
-            self.collaterals.extend(configs.into_iter().map(|(k, v)| (k, v.compress())));
+        pub fn apply_updates(&mut self, ids: Vec<u64>) -> Vec<ConfigUpdate> {
+            let current_time = Clock::current_time_rounded_to_seconds();
+            ids.into_iter().map(|id| {
+                let pending_update = self._remove_pending_update(id);
+                assert!(
+                    current_time.compare(pending_update.activation, TimeComparisonOperator::Gte),
+                    "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_CONFIG_UPDATE_NOT_DUE, current_time.seconds_since_unix_epoch, pending_update.activation.seconds_since_unix_epoch
+                );
+
+                self._apply_update(pending_update.update.clone());
+                pending_update.update
+            }).collect()
         }
 
-        pub fn remove_collateral_config(&mut self, resource: ResourceAddress) {
-            self.collaterals.remove(&resource);
+        pub fn cancel_update(&mut self, id: u64) -> ConfigUpdate {
+            self._remove_pending_update(id).update
+        }
+
+        fn _remove_pending_update(&mut self, id: u64) -> PendingConfigUpdate {
+            let pending_update = self.pending_updates.remove(&id).expect(ERROR_MISSING_CONFIG_UPDATE);
+            self.pending_update_ids.retain(|pending_id| *pending_id != id);
+            pending_update
+        }
+
+        fn _apply_update(&mut self, update: ConfigUpdate) {
+            match update {
+                ConfigUpdate::Exchange(config) => {
+                    self.exchange = config.compress();
+                },
+                ConfigUpdate::Pairs(configs) => {
+                    for config in configs.into_iter() {
+                        self.pairs.insert(config.pair_id.to_owned(), config.compress());
+                    }
+                },
+                ConfigUpdate::Collaterals(configs) => {
+                    self.collaterals.extend(configs.into_iter().map(|(k, v)| (k, v.compress())));
+                },
+                ConfigUpdate::CollateralRemoval(resource) => {
+                    self.collaterals.remove(&resource);
+                },
+                ConfigUpdate::PairRemoval(pair_id) => {
+                    self.pairs.remove(&pair_id);
+                },
+            }
         }
     }
 }
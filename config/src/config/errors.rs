@@ -0,0 +1,4 @@
+pub const ERROR_MISSING_CONFIG_UPDATE: &str = "Config update not found";
+pub const ERROR_CONFIG_UPDATE_NOT_DUE: &str = "Config update not due";
+pub const ERROR_PENDING_CONFIG_UPDATES_FULL: &str = "Too many pending config updates";
+pub const ERROR_ARITHMETIC: &str = "Arithmetic error";
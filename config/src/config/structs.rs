@@ -45,18 +45,42 @@ pub struct ExchangeConfig {
     pub fee_liquidity_add: Decimal,
     /// Fee for removing liquidity
     pub fee_liquidity_remove: Decimal,
+    /// Minimum base token amount of a queued liquidity deposit
+    pub lp_deposit_min: Decimal,
+    /// Minimum LP token amount of a queued liquidity withdrawal
+    pub lp_withdrawal_min: Decimal,
     /// Share of fees that goes to the protocol
     pub fee_share_protocol: Decimal,
     /// Share of fees that goes to the treasury
     pub fee_share_treasury: Decimal,
     /// Share of fees that goes to the referrer
     pub fee_share_referral: Decimal,
+    /// Share of pool fees that goes to locked liquidity
+    pub fee_share_lock: Decimal,
+    /// Share of its pro-rata pool fees that goes to the senior tranche
+    pub fee_share_senior: Decimal,
+    /// Allowed liquidity lock terms in seconds and their fee share boost
+    pub lock_terms: Vec<(i64, Decimal)>,
     /// Maximum fee rate that can be charged
     pub fee_max: Decimal,
     /// Amount to burn for protocol fees
     pub protocol_burn_amount: Decimal,
     /// Keeper reward amount
     pub reward_keeper: Decimal,
+    /// Maximum rebate value paid by the pool for skew reducing trades per period, zero to disable
+    pub rebate_budget: Decimal,
+    /// Length of the rebate budget period in seconds
+    pub rebate_period_seconds: i64,
+    /// Additional oracle components whose prices must agree with the main oracle
+    pub oracle_components: Vec<ComponentAddress>,
+    /// Maximum allowed spread between oracle prices for feeds without a pair config
+    pub price_tolerance: Decimal,
+    /// Minimum delay in seconds before an exchange config update takes effect
+    pub config_delay_exchange: i64,
+    /// Minimum delay in seconds before a pair config update takes effect
+    pub config_delay_pairs: i64,
+    /// Minimum delay in seconds before a collateral config update or removal takes effect
+    pub config_delay_collaterals: i64,
 }
 
 #[derive(ScryptoSbor, Clone)]
@@ -79,18 +103,42 @@ pub struct ExchangeConfigCompressed {
     pub fee_liquidity_add: DFloat16,
     /// Fee for removing liquidity
     pub fee_liquidity_remove: DFloat16,
+    /// Minimum base token amount of a queued liquidity deposit
+    pub lp_deposit_min: DFloat16,
+    /// Minimum LP token amount of a queued liquidity withdrawal
+    pub lp_withdrawal_min: DFloat16,
     /// Share of fees that goes to the protocol
     pub fee_share_protocol: DFloat16,
     /// Share of fees that goes to the treasury
     pub fee_share_treasury: DFloat16,
     /// Share of fees that goes to the referrer
     pub fee_share_referral: DFloat16,
+    /// Share of pool fees that goes to locked liquidity
+    pub fee_share_lock: DFloat16,
+    /// Share of its pro-rata pool fees that goes to the senior tranche
+    pub fee_share_senior: DFloat16,
+    /// Allowed liquidity lock terms in seconds and their fee share boost
+    pub lock_terms: Vec<(i64, DFloat16)>,
     /// Maximum fee rate that can be charged
     pub fee_max: DFloat16,
     /// Amount to burn for protocol fees
     pub protocol_burn_amount: DFloat16,
     /// Keeper reward amount
     pub keeper_reward: DFloat16,
+    /// Maximum rebate value paid by the pool for skew reducing trades per period, zero to disable
+    pub rebate_budget: DFloat16,
+    /// Length of the rebate budget period in seconds
+    pub rebate_period_seconds: i64,
+    /// Additional oracle components whose prices must agree with the main oracle
+    pub oracle_components: Vec<ComponentAddress>,
+    /// Maximum allowed spread between oracle prices for feeds without a pair config
+    pub price_tolerance: DFloat16,
+    /// Minimum delay in seconds before an exchange config update takes effect
+    pub config_delay_exchange: i64,
+    /// Minimum delay in seconds before a pair config update takes effect
+    pub config_delay_pairs: i64,
+    /// Minimum delay in seconds before a collateral config update or removal takes effect
+    pub config_delay_collaterals: i64,
 }
 
 impl Default for ExchangeConfig {
@@ -105,12 +153,28 @@ impl Default for ExchangeConfig {
             adl_b: dec!(0.07),
             fee_liquidity_add: dec!(0.001),
             fee_liquidity_remove: dec!(0.001),
+            lp_deposit_min: dec!(10),
+            lp_withdrawal_min: dec!(10),
             fee_share_protocol: dec!(0.22),
             fee_share_treasury: dec!(0.08),
             fee_share_referral: dec!(1),
+            fee_share_lock: dec!(0),
+            fee_share_senior: dec!(0.5),
+            lock_terms: vec![
+                (2592000, dec!(1)), // 30 days
+                (7776000, dec!(2)), // 90 days
+                (15552000, dec!(3)), // 180 days
+            ],
             fee_max: dec!(0.01),
             protocol_burn_amount: dec!(10000),
             reward_keeper: dec!(1),
+            rebate_budget: dec!(0),
+            rebate_period_seconds: 86400,
+            oracle_components: vec![],
+            price_tolerance: dec!(0.005),
+            config_delay_exchange: 86400,
+            config_delay_pairs: 3600,
+            config_delay_collaterals: 86400,
         }
     }
 }
@@ -124,12 +188,28 @@ impl ExchangeConfig {
         assert!(self.adl_b >= dec!(0), "Invalid adl b");
         assert!(self.fee_liquidity_add >= dec!(0), "Invalid liquidity fee");
         assert!(self.fee_liquidity_remove >= dec!(0), "Invalid liquidity fee");
+        assert!(self.lp_deposit_min >= dec!(0), "Invalid minimum deposit");
+        assert!(self.lp_withdrawal_min >= dec!(0), "Invalid minimum withdrawal");
         assert!(self.fee_share_protocol >= dec!(0) && self.fee_share_protocol <= dec!(0.3), "Invalid protocol fee");
         assert!(self.fee_share_treasury >= dec!(0) && self.fee_share_treasury <= dec!(0.15), "Invalid treasury fee");
         assert!(self.fee_share_referral >= dec!(0) && self.fee_share_referral <= dec!(1), "Invalid referral fee");
+        assert!(self.fee_share_lock >= dec!(0) && self.fee_share_lock <= dec!(0.5), "Invalid lock fee");
+        assert!(self.fee_share_senior >= dec!(0) && self.fee_share_senior <= dec!(1), "Invalid senior fee");
+        assert!(self.lock_terms.len() <= 10, "Too many lock terms");
+        for (term, boost) in self.lock_terms.iter() {
+            assert!(*term > 0, "Invalid lock term");
+            assert!(*boost > dec!(0), "Invalid lock boost");
+        }
         assert!(self.fee_max >= dec!(0), "Invalid max fee");
         assert!(self.protocol_burn_amount >= dec!(1000), "Invalid protocol burn amount");
         assert!(self.reward_keeper >= dec!(0), "Invalid keeper reward");
+        assert!(self.rebate_budget >= dec!(0), "Invalid rebate budget");
+        assert!(self.rebate_period_seconds > 0, "Invalid rebate period");
+        assert!(self.oracle_components.len() <= 4, "Too many oracle components");
+        assert!(self.price_tolerance >= dec!(0) && self.price_tolerance <= dec!(0.1), "Invalid price tolerance");
+        assert!(self.config_delay_exchange >= 3600 && self.config_delay_exchange <= 2592000, "Invalid config delay");
+        assert!(self.config_delay_pairs >= 3600 && self.config_delay_pairs <= 2592000, "Invalid config delay");
+        assert!(self.config_delay_collaterals >= 3600 && self.config_delay_collaterals <= 2592000, "Invalid config delay");
     }
 
     pub fn compress(&self) -> ExchangeConfigCompressed {
@@ -143,12 +223,24 @@ impl ExchangeConfig {
             adl_b: DFloat16::from(self.adl_b),
             fee_liquidity_add: DFloat16::from(self.fee_liquidity_add),
             fee_liquidity_remove: DFloat16::from(self.fee_liquidity_remove),
+            lp_deposit_min: DFloat16::from(self.lp_deposit_min),
+            lp_withdrawal_min: DFloat16::from(self.lp_withdrawal_min),
             fee_share_protocol: DFloat16::from(self.fee_share_protocol),
             fee_share_treasury: DFloat16::from(self.fee_share_treasury),
             fee_share_referral: DFloat16::from(self.fee_share_referral),
+            fee_share_lock: DFloat16::from(self.fee_share_lock),
+            fee_share_senior: DFloat16::from(self.fee_share_senior),
+            lock_terms: self.lock_terms.iter().map(|(term, boost)| (*term, DFloat16::from(*boost))).collect(),
             fee_max: DFloat16::from(self.fee_max),
             protocol_burn_amount: DFloat16::from(self.protocol_burn_amount),
             keeper_reward: DFloat16::from(self.reward_keeper),
+            rebate_budget: DFloat16::from(self.rebate_budget),
+            rebate_period_seconds: self.rebate_period_seconds,
+            oracle_components: self.oracle_components.clone(),
+            price_tolerance: DFloat16::from(self.price_tolerance),
+            config_delay_exchange: self.config_delay_exchange,
+            config_delay_pairs: self.config_delay_pairs,
+            config_delay_collaterals: self.config_delay_collaterals,
         }
     }
 }
@@ -165,12 +257,24 @@ impl ExchangeConfigCompressed {
             adl_b: self.adl_b.into(),
             fee_liquidity_add: self.fee_liquidity_add.into(),
             fee_liquidity_remove: self.fee_liquidity_remove.into(),
+            lp_deposit_min: self.lp_deposit_min.into(),
+            lp_withdrawal_min: self.lp_withdrawal_min.into(),
             fee_share_protocol: self.fee_share_protocol.into(),
             fee_share_treasury: self.fee_share_treasury.into(),
             fee_share_referral: self.fee_share_referral.into(),
+            fee_share_lock: self.fee_share_lock.into(),
+            fee_share_senior: self.fee_share_senior.into(),
+            lock_terms: self.lock_terms.iter().map(|(term, boost)| (*term, (*boost).into())).collect(),
             fee_max: self.fee_max.into(),
             protocol_burn_amount: self.protocol_burn_amount.into(),
             reward_keeper: self.keeper_reward.into(),
+            rebate_budget: self.rebate_budget.into(),
+            rebate_period_seconds: self.rebate_period_seconds,
+            oracle_components: self.oracle_components.clone(),
+            price_tolerance: self.price_tolerance.into(),
+            config_delay_exchange: self.config_delay_exchange,
+            config_delay_pairs: self.config_delay_pairs,
+            config_delay_collaterals: self.config_delay_collaterals,
         }
     }
 }
@@ -211,6 +315,28 @@ pub struct PairConfig {
     pub fee_0: Decimal,
     /// Price impact fee
     pub fee_1: Decimal,
+    /// Maximum allowed oi value for each side as a ratio of pool value, zero to disable
+    pub oi_side_ratio_max: Decimal,
+    /// Maximum allowed combined oi value as a ratio of pool value, zero to disable
+    pub oi_combined_ratio_max: Decimal,
+    /// Lower bound for the pool value based oi caps
+    pub oi_value_floor: Decimal,
+    /// Upper bound for the pool value based oi caps, zero for no bound
+    pub oi_value_ceiling: Decimal,
+    /// Margin tiers as minimum position value, initial margin and maintenance margin
+    pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+    /// Price impact fee model
+    pub fee_model: FeeModel,
+    /// Rebate rate paid on the skew reduction of a trade, zero to disable
+    pub skew_rebate_rate: Decimal,
+    /// Funding model
+    pub funding_model: FundingModel,
+    /// Maximum allowed spread between oracle prices
+    pub price_tolerance: Decimal,
+    /// Averaging period of the mark price in seconds, zero to use the index price
+    pub mark_ema_seconds: i64,
+    /// Lifecycle status of the pair
+    pub status: PairStatus,
 }
 
 #[derive(ScryptoSbor, Clone)]
@@ -249,6 +375,28 @@ pub struct PairConfigCompressed {
     pub fee_0: DFloat16,
     /// Price impact fee
     pub fee_1: DFloat16,
+    /// Maximum allowed oi value for each side as a ratio of pool value, zero to disable
+    pub oi_side_ratio_max: DFloat16,
+    /// Maximum allowed combined oi value as a ratio of pool value, zero to disable
+    pub oi_combined_ratio_max: DFloat16,
+    /// Lower bound for the pool value based oi caps
+    pub oi_value_floor: DFloat16,
+    /// Upper bound for the pool value based oi caps, zero for no bound
+    pub oi_value_ceiling: DFloat16,
+    /// Margin tiers as minimum position value, initial margin and maintenance margin
+    pub margin_tiers: Vec<(DFloat16, DFloat16, DFloat16)>,
+    /// Price impact fee model
+    pub fee_model: FeeModelCompressed,
+    /// Rebate rate paid on the skew reduction of a trade, zero to disable
+    pub skew_rebate_rate: DFloat16,
+    /// Funding model
+    pub funding_model: FundingModelCompressed,
+    /// Maximum allowed spread between oracle prices
+    pub price_tolerance: DFloat16,
+    /// Averaging period of the mark price in seconds, zero to use the index price
+    pub mark_ema_seconds: u16,
+    /// Lifecycle status of the pair
+    pub status: PairStatus,
 }
 
 impl PairConfig {
@@ -269,6 +417,45 @@ impl PairConfig {
         assert!(self.funding_share >= dec!(0) && self.funding_share <= dec!(0.1), "Invalid funding share");
         assert!(self.fee_0 >= dec!(0) && self.fee_0 <= dec!(0.015), "Invalid fee 0");
         assert!(self.fee_1 >= dec!(0) && self.fee_1 <= dec!(0.000001), "Invalid fee 1");
+        assert!(self.oi_side_ratio_max >= dec!(0), "Invalid oi side ratio maximum");
+        assert!(self.oi_combined_ratio_max >= dec!(0), "Invalid oi combined ratio maximum");
+        assert!(self.oi_value_floor >= dec!(0), "Invalid oi value floor");
+        assert!(self.oi_value_ceiling.is_zero() || self.oi_value_ceiling >= self.oi_value_floor, "Invalid oi value ceiling");
+        assert!(self.margin_tiers.len() <= 10, "Too many margin tiers");
+        let mut tier_prev = (dec!(0), self.margin_initial, self.margin_maintenance);
+        for tier in self.margin_tiers.iter() {
+            let (value_min, margin_initial, margin_maintenance) = *tier;
+            assert!(value_min > tier_prev.0, "Invalid margin tier value");
+            assert!(margin_initial >= tier_prev.1 && margin_initial <= dec!(1), "Invalid margin tier initial margin");
+            assert!(margin_maintenance >= tier_prev.2 && margin_maintenance <= margin_initial, "Invalid margin tier maintenance margin");
+            tier_prev = *tier;
+        }
+        self.fee_model.validate();
+        assert!(self.skew_rebate_rate >= dec!(0) && self.skew_rebate_rate <= dec!(0.005), "Invalid skew rebate rate");
+        self.funding_model.validate(&self.pair_id);
+        assert!(self.price_tolerance >= dec!(0) && self.price_tolerance <= dec!(0.1), "Invalid price tolerance");
+        assert!(self.mark_ema_seconds >= 0 && self.mark_ema_seconds <= 3600, "Invalid mark EMA period");
+        self.status.validate();
+    }
+
+    /// Returns the margin tier for a position value with its initial and maintenance margin
+    pub fn margin_tier(&self, value_abs: Decimal) -> (u8, Decimal, Decimal) {
+        self.margin_tiers.iter().enumerate().rev()
+            .find(|(_, (value_min, _, _))| value_abs >= *value_min)
+            .map(|(i, (_, margin_initial, margin_maintenance))| ((i + 1) as u8, *margin_initial, *margin_maintenance))
+            .unwrap_or((0, self.margin_initial, self.margin_maintenance))
+    }
+
+    /// Returns whether the config only moves the current config to a more restrictive trading status, which takes
+    /// effect without a delay, settlement is excluded as it fixes a price
+    pub fn is_restriction_of(&self, current: &PairConfigCompressed) -> bool {
+        let unchanged = PairConfig {
+            status: current.status.to_owned(),
+            ..self.clone()
+        };
+        !matches!(self.status, PairStatus::Settled(_)) &&
+            self.status.restriction() > current.status.restriction() &&
+            scrypto_encode(&unchanged.compress()).unwrap() == scrypto_encode(current).unwrap()
     }
 
     pub fn compress(&self) -> PairConfigCompressed {
@@ -290,6 +477,19 @@ impl PairConfig {
             funding_share: DFloat16::from(self.funding_share),
             fee_0: DFloat16::from(self.fee_0),
             fee_1: DFloat16::from(self.fee_1),
+            oi_side_ratio_max: DFloat16::from(self.oi_side_ratio_max),
+            oi_combined_ratio_max: DFloat16::from(self.oi_combined_ratio_max),
+            oi_value_floor: DFloat16::from(self.oi_value_floor),
+            oi_value_ceiling: DFloat16::from(self.oi_value_ceiling),
+            margin_tiers: self.margin_tiers.iter().map(|(value_min, margin_initial, margin_maintenance)| {
+                (DFloat16::from(*value_min), DFloat16::from(*margin_initial), DFloat16::from(*margin_maintenance))
+            }).collect(),
+            fee_model: self.fee_model.compress(),
+            skew_rebate_rate: DFloat16::from(self.skew_rebate_rate),
+            funding_model: self.funding_model.compress(),
+            price_tolerance: DFloat16::from(self.price_tolerance),
+            mark_ema_seconds: self.mark_ema_seconds as u16,
+            status: self.status.to_owned(),
         }
     }
 }
@@ -314,6 +514,193 @@ impl PairConfigCompressed {
             funding_share: self.funding_share.into(),
             fee_0: self.fee_0.into(),
             fee_1: self.fee_1.into(),
+            oi_side_ratio_max: self.oi_side_ratio_max.into(),
+            oi_combined_ratio_max: self.oi_combined_ratio_max.into(),
+            oi_value_floor: self.oi_value_floor.into(),
+            oi_value_ceiling: self.oi_value_ceiling.into(),
+            margin_tiers: self.margin_tiers.iter().map(|(value_min, margin_initial, margin_maintenance)| {
+                ((*value_min).into(), (*margin_initial).into(), (*margin_maintenance).into())
+            }).collect(),
+            fee_model: self.fee_model.decompress(),
+            skew_rebate_rate: self.skew_rebate_rate.into(),
+            funding_model: self.funding_model.decompress(),
+            price_tolerance: self.price_tolerance.into(),
+            mark_ema_seconds: self.mark_ema_seconds as i64,
+            status: self.status.to_owned(),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug, PartialEq)]
+pub enum PairStatus {
+    /// Pair can be traded
+    Active,
+    /// Only orders that reduce existing positions can be executed
+    ReduceOnly,
+    /// No execution other than request cancellation
+    Halted,
+    /// Remaining positions are closed by keepers at the final settlement price
+    Settled(Decimal),
+}
+
+impl PairStatus {
+    pub fn validate(&self) {
+        if let PairStatus::Settled(price) = self {
+            assert!(*price > dec!(0), "Invalid settlement price");
+        }
+    }
+
+    /// Level of trading restriction, from active to settled
+    pub fn restriction(&self) -> u8 {
+        match self {
+            PairStatus::Active => 0,
+            PairStatus::ReduceOnly => 1,
+            PairStatus::Halted => 2,
+            PairStatus::Settled(_) => 3,
+        }
+    }
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug)]
+pub enum FeeModel {
+    /// Price impact fee of fee 1 times the change in squared skew
+    LinearSkew,
+    /// Price impact fee rate interpolated linearly between skew and fee rate points, starting at zero skew
+    PiecewiseLinear(Vec<(Decimal, Decimal)>),
+    /// Price impact fee rate of the coefficient times the square root of skew
+    Sqrt(Decimal),
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum FeeModelCompressed {
+    /// Price impact fee of fee 1 times the change in squared skew
+    LinearSkew,
+    /// Price impact fee rate interpolated linearly between skew and fee rate points, starting at zero skew
+    PiecewiseLinear(Vec<(DFloat16, DFloat16)>),
+    /// Price impact fee rate of the coefficient times the square root of skew
+    Sqrt(DFloat16),
+}
+
+impl FeeModel {
+    pub fn validate(&self) {
+        match self {
+            FeeModel::LinearSkew => {},
+            FeeModel::PiecewiseLinear(points) => {
+                assert!(!points.is_empty() && points.len() <= 10, "Invalid number of fee curve points");
+                assert!(points[0].0.is_zero(), "Invalid first fee curve point");
+                for window in points.windows(2) {
+                    assert!(window[1].0 > window[0].0, "Invalid fee curve skew");
+                    assert!(window[1].1 >= window[0].1, "Invalid fee curve rate");
+                }
+                for (_, rate) in points.iter() {
+                    assert!(*rate >= dec!(0) && *rate <= dec!(0.1), "Invalid fee curve rate");
+                }
+            },
+            FeeModel::Sqrt(coefficient) => {
+                assert!(*coefficient >= dec!(0) && *coefficient <= dec!(0.0001), "Invalid fee sqrt coefficient");
+            },
+        }
+    }
+
+    pub fn compress(&self) -> FeeModelCompressed {
+        match self {
+            FeeModel::LinearSkew => FeeModelCompressed::LinearSkew,
+            FeeModel::PiecewiseLinear(points) => FeeModelCompressed::PiecewiseLinear(
+                points.iter().map(|(skew, rate)| (DFloat16::from(*skew), DFloat16::from(*rate))).collect()
+            ),
+            FeeModel::Sqrt(coefficient) => FeeModelCompressed::Sqrt(DFloat16::from(*coefficient)),
+        }
+    }
+}
+
+impl FeeModelCompressed {
+    pub fn decompress(&self) -> FeeModel {
+        match self {
+            FeeModelCompressed::LinearSkew => FeeModel::LinearSkew,
+            FeeModelCompressed::PiecewiseLinear(points) => FeeModel::PiecewiseLinear(
+                points.iter().map(|(skew, rate)| ((*skew).into(), (*rate).into())).collect()
+            ),
+            FeeModelCompressed::Sqrt(coefficient) => FeeModel::Sqrt((*coefficient).into()),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug)]
+pub enum FundingModel {
+    /// Skew based funding from funding 1 and the funding 2 integral
+    Skew,
+    /// Funding tracking the premium of an external mark price over the index price
+    Premium(PremiumFunding),
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum FundingModelCompressed {
+    /// Skew based funding from funding 1 and the funding 2 integral
+    Skew,
+    /// Funding tracking the premium of an external mark price over the index price
+    Premium(PremiumFundingCompressed),
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug)]
+pub struct PremiumFunding {
+    /// Price feed id of the external mark price
+    pub mark_pair_id: PairId,
+    /// Annualized funding rate per unit of premium
+    pub funding_premium: Decimal,
+    /// Premium dead band subtracted before funding is charged
+    pub premium_dampener: Decimal,
+    /// Maximum absolute premium after dampening
+    pub premium_max: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PremiumFundingCompressed {
+    /// Price feed id of the external mark price
+    pub mark_pair_id: PairId,
+    /// Annualized funding rate per unit of premium
+    pub funding_premium: DFloat16,
+    /// Premium dead band subtracted before funding is charged
+    pub premium_dampener: DFloat16,
+    /// Maximum absolute premium after dampening
+    pub premium_max: DFloat16,
+}
+
+impl FundingModel {
+    pub fn validate(&self, pair_id: &PairId) {
+        match self {
+            FundingModel::Skew => {},
+            FundingModel::Premium(premium) => {
+                assert!(premium.mark_pair_id != *pair_id, "Invalid funding mark pair");
+                assert!(premium.funding_premium >= dec!(0) && premium.funding_premium <= dec!(2000), "Invalid funding premium");
+                assert!(premium.premium_dampener >= dec!(0) && premium.premium_dampener <= dec!(0.1), "Invalid premium dampener");
+                assert!(premium.premium_max > dec!(0) && premium.premium_max <= dec!(0.5), "Invalid premium maximum");
+            },
+        }
+    }
+
+    pub fn compress(&self) -> FundingModelCompressed {
+        match self {
+            FundingModel::Skew => FundingModelCompressed::Skew,
+            FundingModel::Premium(premium) => FundingModelCompressed::Premium(PremiumFundingCompressed {
+                mark_pair_id: premium.mark_pair_id.to_owned(),
+                funding_premium: DFloat16::from(premium.funding_premium),
+                premium_dampener: DFloat16::from(premium.premium_dampener),
+                premium_max: DFloat16::from(premium.premium_max),
+            }),
+        }
+    }
+}
+
+impl FundingModelCompressed {
+    pub fn decompress(&self) -> FundingModel {
+        match self {
+            FundingModelCompressed::Skew => FundingModel::Skew,
+            FundingModelCompressed::Premium(premium) => FundingModel::Premium(PremiumFunding {
+                mark_pair_id: premium.mark_pair_id.to_owned(),
+                funding_premium: premium.funding_premium.into(),
+                premium_dampener: premium.premium_dampener.into(),
+                premium_max: premium.premium_max.into(),
+            }),
         }
     }
 }
@@ -369,3 +756,48 @@ impl CollateralConfigCompressed {
         }
     }
 }
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug)]
+pub enum ConfigUpdate {
+    Exchange(ExchangeConfig),
+    Pairs(Vec<PairConfig>),
+    Collaterals(Vec<(ResourceAddress, CollateralConfig)>),
+    CollateralRemoval(ResourceAddress),
+    PairRemoval(PairId),
+}
+
+impl ConfigUpdate {
+    pub fn validate(&self) {
+        match self {
+            ConfigUpdate::Exchange(config) => config.validate(),
+            ConfigUpdate::Pairs(configs) => configs.iter().for_each(|config| config.validate()),
+            ConfigUpdate::Collaterals(configs) => configs.iter().for_each(|(_, config)| config.validate()),
+            ConfigUpdate::CollateralRemoval(_) | ConfigUpdate::PairRemoval(_) => {},
+        }
+    }
+
+    /// Minimum delay in seconds before the update can take effect, an exchange config update that reduces a delay
+    /// also waits out the delay it reduces, pair status restrictions are not delayed by the config
+    pub fn delay(&self, exchange: &ExchangeConfig) -> i64 {
+        match self {
+            ConfigUpdate::Exchange(config) => {
+                let mut delay = exchange.config_delay_exchange;
+                if config.config_delay_pairs < exchange.config_delay_pairs {
+                    delay = delay.max(exchange.config_delay_pairs);
+                }
+                if config.config_delay_collaterals < exchange.config_delay_collaterals {
+                    delay = delay.max(exchange.config_delay_collaterals);
+                }
+                delay
+            },
+            ConfigUpdate::Pairs(_) | ConfigUpdate::PairRemoval(_) => exchange.config_delay_pairs,
+            ConfigUpdate::Collaterals(_) | ConfigUpdate::CollateralRemoval(_) => exchange.config_delay_collaterals,
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PendingConfigUpdate {
+    pub update: ConfigUpdate,
+    pub activation: Instant,
+}
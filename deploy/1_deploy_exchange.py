@@ -14,7 +14,7 @@ load_dotenv()
 from tools.gateway import Gateway
 from tools.accounts import new_account, load_account
 from tools.manifests import lock_fee, deposit_all, mint_owner_badge, mint_authority, mint_base_authority
-from tools.manifests import create_base, mint_protocol_resource, create_keeper_reward, create_lp, create_referral_str, create_recovery_key_str
+from tools.manifests import create_base, mint_protocol_resource, create_keeper_reward, create_lp, create_junior_lp, create_referral_str, create_recovery_key_str
 timestamp = datetime.datetime.now().strftime("%Y%m%d%H")
 
 def clean(name: str) -> None:
@@ -228,6 +228,19 @@ async def main():
             envs.append(('LP_RESOURCE', lp_resource))
             print('LP_RESOURCE:', lp_resource)
 
+            if 'JUNIOR_LP_RESOURCE' not in config_data:
+                builder = ret.ManifestBuilder()
+                builder = lock_fee(builder, account, 100)
+                builder = create_junior_lp(builder, owner_role, authority_resource)
+                payload, intent = await gateway.build_transaction(builder, public_key, private_key)
+                await gateway.submit_transaction(payload)
+                addresses = await gateway.get_new_addresses(intent)
+                config_data['JUNIOR_LP_RESOURCE'] = addresses[0]
+
+            junior_lp_resource = config_data['JUNIOR_LP_RESOURCE']
+            envs.append(('JUNIOR_LP_RESOURCE', junior_lp_resource))
+            print('JUNIOR_LP_RESOURCE:', junior_lp_resource)
+
             if 'REFERRAL_RESOURCE' not in config_data:
                 manifest = create_referral_str(account, owner_amount, owner_resource, authority_resource)
                 payload, intent = await gateway.build_transaction_str(manifest, public_key, private_key)
@@ -393,10 +406,12 @@ async def main():
             print('ORACLE_PACKAGE:', oracle_package)
 
             if 'ORACLE_COMPONENT' not in config_data:
-                oracle_key_bytes_0 = ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.U8_VALUE, 
-                    [ret.ManifestBuilderValue.U8_VALUE(b) for b in bytes.fromhex(oracle_key_0)])
-                oracle_key_bytes_1 = ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.U8_VALUE, 
-                    [ret.ManifestBuilderValue.U8_VALUE(b) for b in bytes.fromhex(oracle_key_1)])
+                oracle_key_bytes_0 = ret.ManifestBuilderValue.ENUM_VALUE(0, 
+                    [ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.U8_VALUE, 
+                        [ret.ManifestBuilderValue.U8_VALUE(b) for b in bytes.fromhex(oracle_key_0)])])
+                oracle_key_bytes_1 = ret.ManifestBuilderValue.ENUM_VALUE(0, 
+                    [ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.U8_VALUE, 
+                        [ret.ManifestBuilderValue.U8_VALUE(b) for b in bytes.fromhex(oracle_key_1)])])
                 builder = ret.ManifestBuilder()
                 builder = lock_fee(builder, account, 100)
                 builder = builder.call_function(
@@ -405,7 +420,7 @@ async def main():
                     'new',
                     [
                         manifest_owner_role, 
-                        ret.ManifestBuilderValue.MAP_VALUE(ret.ManifestBuilderValueKind.U64_VALUE, ret.ManifestBuilderValueKind.ARRAY_VALUE, [
+                        ret.ManifestBuilderValue.MAP_VALUE(ret.ManifestBuilderValueKind.U64_VALUE, ret.ManifestBuilderValueKind.ENUM_VALUE, [
                             ret.ManifestBuilderMapEntry(ret.ManifestBuilderValue.U64_VALUE(0), oracle_key_bytes_0),
                             ret.ManifestBuilderMapEntry(ret.ManifestBuilderValue.U64_VALUE(1), oracle_key_bytes_1)
                         ])
@@ -768,6 +783,10 @@ async def main():
                             "lp_resource",
                             "{lp_resource}"
                         ),
+                        Tuple(
+                            "junior_lp_resource",
+                            "{junior_lp_resource}"
+                        ),
                         Tuple(
                             "referral_resource",
                             "{referral_resource}"
@@ -846,6 +865,7 @@ async def main():
             print(f'BASE_AUTHORITY_RESOURCE={base_authority_resource}')
             print(f'BASE_RESOURCE={base_resource}')
             print(f'LP_RESOURCE={lp_resource}')
+            print(f'JUNIOR_LP_RESOURCE={junior_lp_resource}')
             print(f'REFERRAL_RESOURCE={referral_resource}')
             print(f'RECOVERY_KEY_RESOURCE={recovery_key_resource}')
             print(f'PROTOCOL_RESOURCE={protocol_resource}')
@@ -48,6 +48,7 @@ async def main():
         data = {
             "BASE_RESOURCE": config_data['BASE_RESOURCE'],
             "LP_RESOURCE": config_data['LP_RESOURCE'],
+            "JUNIOR_LP_RESOURCE": config_data['JUNIOR_LP_RESOURCE'],
             "PROTOCOL_RESOURCE": config_data['PROTOCOL_RESOURCE'],
             "REFERRAL_RESOURCE": config_data['REFERRAL_RESOURCE'],
             "RECOVERY_KEY_RESOURCE": config_data['RECOVERY_KEY_RESOURCE'],
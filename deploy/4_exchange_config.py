@@ -67,12 +67,28 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.07')), # adl_b
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0')), # fee_liquidity_add
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.001')), # fee_liquidity_remove
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('10')), # lp_deposit_min
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('10')), # lp_withdrawal_min
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.2')), # fee_share_protocol
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.1')), # fee_share_treasury
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('1')), # fee_share_referral
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0')), # fee_share_lock
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.5')), # fee_share_senior
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, [ # lock_terms
+                        ret.ManifestBuilderValue.TUPLE_VALUE([ret.ManifestBuilderValue.I64_VALUE(2592000), ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('1'))]),
+                        ret.ManifestBuilderValue.TUPLE_VALUE([ret.ManifestBuilderValue.I64_VALUE(7776000), ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('2'))]),
+                        ret.ManifestBuilderValue.TUPLE_VALUE([ret.ManifestBuilderValue.I64_VALUE(15552000), ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('3'))]),
+                    ]),
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.01')), # fee_max
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('5000')), # protocol_burn_amount
                     ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('1')), # reward_keeper
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0')), # rebate_budget
+                    ret.ManifestBuilderValue.I64_VALUE(86400), # rebate_period_seconds
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.ADDRESS_VALUE, []), # oracle_components
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(ret.Decimal('0.005')), # price_tolerance
+                    ret.ManifestBuilderValue.I64_VALUE(86400), # config_delay_exchange
+                    ret.ManifestBuilderValue.I64_VALUE(3600), # config_delay_pairs
+                    ret.ManifestBuilderValue.I64_VALUE(86400), # config_delay_collaterals
                 ])
             ]
         )
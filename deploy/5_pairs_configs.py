@@ -72,6 +72,13 @@ async def main():
         fee_1_low = ret.Decimal('0.00000001')
         fee_1_mid = ret.Decimal('0.00000002')
         fee_1_high = ret.Decimal('0.0000005')
+        oi_side_ratio_max = ret.Decimal('0')
+        oi_combined_ratio_max = ret.Decimal('0')
+        oi_value_floor = ret.Decimal('0')
+        oi_value_ceiling = ret.Decimal('0')
+        skew_rebate_rate = ret.Decimal('0')
+        price_tolerance = ret.Decimal('0.005')
+        mark_ema_seconds = 0
 
         builder = ret.ManifestBuilder()
         builder = lock_fee(builder, account, 100)
@@ -101,7 +108,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_low)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_low),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('ETH/USD'), # pub pair_id: PairId,
@@ -120,7 +138,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_low)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_low),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('SOL/USD'), # pub pair_id: PairId,
@@ -139,7 +168,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_low)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_low),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('XRD/USD'), # pub pair_id: PairId,
@@ -158,7 +198,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0_high),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_high)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_high),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('SUI/USD'), # pub pair_id: PairId,
@@ -177,7 +228,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('DOGE/USD'), # pub pair_id: PairId,
@@ -196,7 +258,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('ADA/USD'), # pub pair_id: PairId,
@@ -215,7 +288,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('BNB/USD'), # pub pair_id: PairId,
@@ -234,7 +318,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('XRP/USD'), # pub pair_id: PairId,
@@ -253,7 +348,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('PEPE/USD'), # pub pair_id: PairId,
@@ -272,7 +378,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('LINK/USD'), # pub pair_id: PairId,
@@ -291,7 +408,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
                 ret.ManifestBuilderValue.TUPLE_VALUE([
                     ret.ManifestBuilderValue.STRING_VALUE('HYPE/USD'), # pub pair_id: PairId,
@@ -310,7 +438,18 @@ async def main():
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_pool_1),  # pub funding_pool_1: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(funding_share),  # pub funding_share: Decimal,
                     ret.ManifestBuilderValue.DECIMAL_VALUE(fee_0),  # pub fee_0: Decimal,
-                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid)  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(fee_1_mid),  # pub fee_1: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_side_ratio_max),  # pub oi_side_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_combined_ratio_max),  # pub oi_combined_ratio_max: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_floor),  # pub oi_value_floor: Decimal,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(oi_value_ceiling),  # pub oi_value_ceiling: Decimal,
+                    ret.ManifestBuilderValue.ARRAY_VALUE(ret.ManifestBuilderValueKind.TUPLE_VALUE, []),  # pub margin_tiers: Vec<(Decimal, Decimal, Decimal)>,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub fee_model: FeeModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(skew_rebate_rate),  # pub skew_rebate_rate: Decimal,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub funding_model: FundingModel,
+                    ret.ManifestBuilderValue.DECIMAL_VALUE(price_tolerance),  # pub price_tolerance: Decimal,
+                    ret.ManifestBuilderValue.I64_VALUE(mark_ema_seconds),  # pub mark_ema_seconds: i64,
+                    ret.ManifestBuilderValue.ENUM_VALUE(0, []),  # pub status: PairStatus,
                 ]),
             ])]
         )
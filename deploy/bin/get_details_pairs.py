@@ -89,6 +89,17 @@ async def main():
                 'funding_share': float(pair_config[14]['value']),
                 'fee_0': float(pair_config[15]['value']),
                 'fee_1': float(pair_config[16]['value']),
+                'oi_side_ratio_max': float(pair_config[17]['value']),
+                'oi_combined_ratio_max': float(pair_config[18]['value']),
+                'oi_value_floor': float(pair_config[19]['value']),
+                'oi_value_ceiling': float(pair_config[20]['value']),
+                'margin_tiers': [[float(field['value']) for field in tier['fields']] for tier in pair_config[21]['elements']],
+                'fee_model': int(pair_config[22]['variant_id']),
+                'skew_rebate_rate': float(pair_config[23]['value']),
+                'funding_model': int(pair_config[24]['variant_id']),
+                'price_tolerance': float(pair_config[25]['value']),
+                'mark_ema_seconds': int(pair_config[26]['value']),
+                'status': int(pair_config[27]['variant_id']),
             }
 
             price = prices[pair]
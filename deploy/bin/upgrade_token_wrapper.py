@@ -140,6 +140,7 @@ async def main():
         base_authority_resource = config_data['BASE_AUTHORITY_RESOURCE']
         base_resource = config_data['BASE_RESOURCE']
         lp_resource = config_data['LP_RESOURCE']
+        junior_lp_resource = config_data['JUNIOR_LP_RESOURCE']
         referral_resource = config_data['REFERRAL_RESOURCE']
         recovery_key_resource = config_data['RECOVERY_KEY_RESOURCE']
         protocol_resource = config_data['PROTOCOL_RESOURCE']
@@ -186,6 +187,7 @@ async def main():
             ('BASE_AUTHORITY_RESOURCE', base_authority_resource),
             ('BASE_RESOURCE', base_resource),
             ('LP_RESOURCE', lp_resource),
+            ('JUNIOR_LP_RESOURCE', junior_lp_resource),
             ('REFERRAL_RESOURCE', referral_resource),
             ('RECOVERY_KEY_RESOURCE', recovery_key_resource),
             ('PROTOCOL_RESOURCE', protocol_resource),
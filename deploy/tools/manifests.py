@@ -169,6 +169,42 @@ def create_lp(builder: ManifestBuilder, owner_role: OwnerRole, authority_resourc
         address_reservation=None,
     )
 
+def create_junior_lp(builder: ManifestBuilder, owner_role: OwnerRole, authority_resource: str) -> ManifestBuilder:
+    resource_roles: FungibleResourceRoles = FungibleResourceRoles(
+        mint_roles=ResourceManagerRole(
+            role=AccessRule.require(ResourceOrNonFungible.RESOURCE(Address(authority_resource))), 
+            role_updater=AccessRule.deny_all()
+        ),
+        burn_roles=ResourceManagerRole(
+            role=AccessRule.require(ResourceOrNonFungible.RESOURCE(Address(authority_resource))), 
+            role_updater=AccessRule.deny_all()
+        ),
+        freeze_roles=None,
+        recall_roles=None,
+        withdraw_roles=None,
+        deposit_roles=None,
+    )
+    metadata = MetadataModuleConfig(
+        init={
+            'name': MetadataInitEntry(MetadataValue.STRING_VALUE('Surge Junior LP'), False),
+            'symbol': MetadataInitEntry(MetadataValue.STRING_VALUE('SJLP'), False),
+            'description': MetadataInitEntry(MetadataValue.STRING_VALUE('Surge liquidity pool junior tranche LP token.'), False),
+            'icon_url': MetadataInitEntry(MetadataValue.URL_VALUE('https://surge.trade/images/surge_lp_token.png'), False),
+            'info_url': MetadataInitEntry(MetadataValue.URL_VALUE('https://surge.trade'), False),
+        },
+        roles={},
+    )
+
+    return builder.create_fungible_resource_manager(
+        owner_role=owner_role,
+        track_total_supply=True,
+        divisibility=18,
+        initial_supply=None,
+        resource_roles=resource_roles,
+        metadata=metadata,
+        address_reservation=None,
+    )
+
 def create_referral_str(account: Address, owner_amount: str, owner_resource: str, authority_resource: str) -> str:
     return f'''
 CALL_METHOD
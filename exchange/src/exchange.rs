@@ -31,11 +31,20 @@ use self::virtual_oracle::*;
     EventPairConfigUpdates,
     EventCollateralConfigUpdates,
     EventCollateralConfigRemoval,
+    EventPairConfigRemoval,
+    EventConfigUpdateScheduled,
+    EventConfigUpdateActivated,
+    EventConfigUpdateCancelled,
     EventPairUpdates,
     EventAccountCreation,
     EventRequests,
     EventValidRequestsStart,
     EventLiquidityChange,
+    EventJuniorLiquidityChange,
+    EventLiquidityDeposit,
+    EventLiquidityWithdrawal,
+    EventLiquidityLock,
+    EventLiquidityLockClaim,
     EventAddCollateral,
     EventRemoveCollateral,
     EventMarginOrder,
@@ -43,11 +52,13 @@ use self::virtual_oracle::*;
     EventLiquidate,
     EventLiquidateToMargin,
     EventAutoDeleverage,
+    EventSettlePosition,
 )]
 mod exchange_mod {
     const AUTHORITY_RESOURCE: ResourceAddress = _AUTHORITY_RESOURCE;
     const BASE_RESOURCE: ResourceAddress = _BASE_RESOURCE;
     const LP_RESOURCE: ResourceAddress = _LP_RESOURCE;
+    const JUNIOR_LP_RESOURCE: ResourceAddress = _JUNIOR_LP_RESOURCE;
     const PROTOCOL_RESOURCE: ResourceAddress = _PROTOCOL_RESOURCE;
     const KEEPER_REWARD_RESOURCE: ResourceAddress = _KEEPER_REWARD_RESOURCE;
     const REFERRAL_RESOURCE: ResourceAddress = _REFERRAL_RESOURCE;
@@ -66,15 +77,18 @@ mod exchange_mod {
         ORACLE_PACKAGE,
         Oracle {
             // Constructor
-            // fn new(owner_role: OwnerRole, public_key: Bls12381G1PublicKey) -> Global<Oracle>;
+            // fn new(owner_role: OwnerRole, keys: HashMap<ListIndex, OracleKey>) -> Global<Oracle>;
 
             // Authority protected methods
-            fn push_and_get_prices_with_auth(&self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: Bls12381G2Signature, key_id: ListIndex) -> HashMap<PairId, (Decimal, Instant)>;
-            fn get_prices_with_auth(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant)>;
+            fn push_and_get_prices_with_auth(&self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: OracleSignature, key_ids: Vec<ListIndex>) -> HashMap<PairId, (Decimal, Instant, Decimal)>;
+            fn get_prices_with_auth(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant, Decimal)>;
+            fn get_high_low_with_auth(&self, pair_id: PairId, from: Instant, to: Instant) -> Option<HighLow>;
 
             // User methods
-            // fn push_and_get_prices(&self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: Bls12381G2Signature) -> HashMap<PairId, (Decimal, Instant)>;
+            // fn push_and_get_prices(&self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: OracleSignature, key_ids: Vec<ListIndex>) -> HashMap<PairId, (Decimal, Instant)>;
             // fn get_prices(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant)>;
+            // fn get_price_at(&self, pair_id: PairId, timestamp: Instant) -> Option<(Decimal, Instant)>;
+            // fn get_high_low(&self, pair_id: PairId, from: Instant, to: Instant) -> Option<(Decimal, Decimal)>;
         }
     }
     extern_blueprint! {
@@ -88,12 +102,12 @@ mod exchange_mod {
             fn get_pair_configs(&self, n: ListIndex, start: Option<ListIndex>) -> Vec<PairConfigCompressed>;
             fn get_pair_configs_by_ids(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, Option<PairConfigCompressed>>;
             fn get_pair_configs_len(&self) -> ListIndex;
+            fn get_pending_updates(&self) -> Vec<(u64, PendingConfigUpdate)>;
 
             // Authority protected methods
-            fn update_exchange_config(&mut self, config: ExchangeConfig);
-            fn update_pair_configs(&mut self, configs: Vec<PairConfig>);
-            fn update_collateral_configs(&mut self, configs: Vec<(ResourceAddress, CollateralConfig)>);
-            fn remove_collateral_config(&mut self, resource: ResourceAddress);
+            fn schedule_update(&mut self, update: ConfigUpdate) -> (u64, Instant);
+            fn apply_updates(&mut self, ids: Vec<u64>) -> Vec<ConfigUpdate>;
+            fn cancel_update(&mut self, id: u64) -> ConfigUpdate;
         }
     }
     extern_blueprint! {
@@ -126,13 +140,30 @@ mod exchange_mod {
 
             // Getter methods
             fn get_info(&self, pair_ids: HashSet<PairId>) -> MarginPoolInfo;
-            // fn get_position(&self, pair_id: PairId) -> PoolPosition;
+            fn get_position(&self, pair_id: PairId) -> PoolPosition;
             fn get_positions(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, PoolPosition>;     
+            // fn get_deposit(&self, index: ListIndex) -> Option<LpDeposit>;
+            fn get_deposits(&self, n: ListIndex, start: Option<ListIndex>) -> Vec<(ListIndex, LpDeposit)>;
+            // fn get_withdrawal(&self, index: ListIndex) -> Option<LpWithdrawal>;
+            fn get_withdrawals(&self, n: ListIndex, start: Option<ListIndex>) -> Vec<(ListIndex, LpWithdrawal)>;
+            // fn get_pair_history(&self, pair_id: PairId, start: Instant, end: Instant) -> Vec<PairCheckpoint>;
 
             // Authority protected methods
             fn update(&self, update: MarginPoolUpdates);
+            fn remove_position(&self, pair_id: PairId);
             fn deposit(&self, token: Bucket);
-            fn withdraw(&self, amount: Decimal, withdraw_strategy: WithdrawStrategy) -> Bucket;            
+            fn withdraw(&self, amount: Decimal, withdraw_strategy: WithdrawStrategy) -> Bucket;
+            fn queue_deposit(&self, token: Bucket) -> (Bucket, ListIndex);
+            fn cancel_deposit(&self, receipt: Bucket) -> (Bucket, ListIndex);
+            fn fill_deposit(&self, index: ListIndex, lp_token: Bucket) -> Bucket;
+            fn claim_deposit(&self, receipt: Bucket) -> (Bucket, ListIndex);
+            fn queue_withdrawal(&self, lp_token: Bucket, priority: i64) -> (Bucket, ListIndex);
+            fn cancel_withdrawal(&self, receipt: Bucket) -> (Bucket, ListIndex);
+            fn fill_withdrawal(&self, index: ListIndex, token: Bucket) -> Bucket;
+            fn claim_withdrawal(&self, receipt: Bucket) -> (Bucket, ListIndex);
+            fn lock(&self, lp_token: Bucket, term: i64, boost: Decimal) -> (Bucket, ListIndex);
+            fn claim_lock_rewards(&self, index: ListIndex, reward_max: Decimal) -> Decimal;
+            fn unlock(&self, receipt: Bucket, reward_max: Decimal) -> (Bucket, Option<Bucket>, Decimal, i64, ListIndex);
         }
     }
     extern_blueprint! {
@@ -217,6 +248,7 @@ mod exchange_mod {
 
             protocol_swap_user => updatable_by: [OWNER, user_admin];
             liquidity_user => updatable_by: [OWNER, user_admin];
+            liquidity_request_user => updatable_by: [OWNER, user_admin];
             referral_user => updatable_by: [OWNER, user_admin];
             account_creation_user => updatable_by: [OWNER, user_admin];
             account_management_user => updatable_by: [OWNER, user_admin];
@@ -228,7 +260,10 @@ mod exchange_mod {
             keeper_swap_debt => updatable_by: [OWNER];
             keeper_liquidate => updatable_by: [OWNER];
             keeper_auto_deleverage => updatable_by: [OWNER];
+            keeper_settle => updatable_by: [OWNER];
             keeper_update_pairs => updatable_by: [OWNER];
+            keeper_process_liquidity => updatable_by: [OWNER];
+            keeper_apply_config => updatable_by: [OWNER];
         },
         methods { 
             // Owner methods
@@ -239,6 +274,8 @@ mod exchange_mod {
             update_pair_configs => restrict_to: [OWNER];
             update_collateral_configs => restrict_to: [OWNER];
             remove_collateral_config => restrict_to: [OWNER];
+            remove_pair_config => restrict_to: [OWNER];
+            cancel_config_update => restrict_to: [OWNER];
 
             // Admin methods
             collect_treasury => restrict_to: [treasury_admin];
@@ -254,12 +291,14 @@ mod exchange_mod {
             get_account_details => PUBLIC;
             get_pool_details => PUBLIC;
             get_pair_details => PUBLIC;
+            get_pair_funding_preview => PUBLIC;
             get_referral_details => PUBLIC;
             get_referral_code_details => PUBLIC;
             get_exchange_config => PUBLIC;
             get_pair_configs => PUBLIC;
             get_pair_configs_len => PUBLIC;
             get_collateral_configs => PUBLIC;
+            get_pending_config_updates => PUBLIC;
             get_collaterals => PUBLIC;
             get_protocol_balance => PUBLIC;
             get_treasury_balance => PUBLIC;
@@ -268,6 +307,18 @@ mod exchange_mod {
             swap_protocol_fee => restrict_to: [protocol_swap_user];
             add_liquidity => restrict_to: [liquidity_user];
             remove_liquidity => restrict_to: [liquidity_user];
+            add_junior_liquidity => restrict_to: [liquidity_user];
+            remove_junior_liquidity => restrict_to: [liquidity_user];
+            queue_add_liquidity => restrict_to: [liquidity_request_user];
+            cancel_add_liquidity => restrict_to: [liquidity_request_user];
+            claim_add_liquidity => restrict_to: [liquidity_request_user];
+            queue_remove_liquidity => restrict_to: [liquidity_request_user];
+            cancel_remove_liquidity => restrict_to: [liquidity_request_user];
+            claim_remove_liquidity => restrict_to: [liquidity_request_user];
+            queue_remove_locked_liquidity => restrict_to: [liquidity_request_user];
+            lock_liquidity => restrict_to: [liquidity_user];
+            claim_liquidity_lock_rewards => restrict_to: [liquidity_user];
+            unlock_liquidity => restrict_to: [liquidity_user];
             create_referral_codes => restrict_to: [referral_user];
             create_referral_codes_from_allocation => restrict_to: [referral_user];
             collect_referral_rewards => restrict_to: [referral_user];
@@ -290,7 +341,10 @@ mod exchange_mod {
             liquidate => restrict_to: [keeper_liquidate];
             liquidate_to_margin => restrict_to: [keeper_liquidate];
             auto_deleverage => restrict_to: [keeper_auto_deleverage];
+            settle_position => restrict_to: [keeper_settle];
             update_pairs => restrict_to: [keeper_update_pairs];
+            process_liquidity_requests => restrict_to: [keeper_process_liquidity];
+            apply_config_updates => restrict_to: [keeper_apply_config];
         }
     }
 
@@ -343,6 +397,7 @@ mod exchange_mod {
                 
                 protocol_swap_user => rule!(allow_all);
                 liquidity_user => rule!(allow_all);
+                liquidity_request_user => rule!(allow_all);
                 referral_user => rule!(allow_all);
                 account_creation_user => rule!(allow_all);
                 account_management_user => rule!(allow_all);
@@ -354,7 +409,10 @@ mod exchange_mod {
                 keeper_swap_debt => rule!(allow_all);
                 keeper_liquidate => rule!(allow_all);
                 keeper_auto_deleverage => rule!(allow_all);
+                keeper_settle => rule!(allow_all);
                 keeper_update_pairs => rule!(allow_all);
+                keeper_process_liquidity => rule!(allow_all);
+                keeper_apply_config => rule!(allow_all);
             })
             .with_address(component_reservation)
             .globalize()
@@ -388,51 +446,47 @@ mod exchange_mod {
             &mut self, 
             config: ExchangeConfig,
         ) {
-            authorize!(self, {
-                Global::<Config>::from(CONFIG_COMPONENT).update_exchange_config(config.clone());
-            });
-                
-            Runtime::emit_event(EventExchangeConfigUpdate {
-                config,
-            });
+            self._schedule_config_update(ConfigUpdate::Exchange(config));
         }
 
         pub fn update_pair_configs(
             &mut self, 
             configs: Vec<PairConfig>,
         ) {
-            authorize!(self, {
-                Global::<Config>::from(CONFIG_COMPONENT).update_pair_configs(configs.clone());
-            });
-
-            Runtime::emit_event(EventPairConfigUpdates {
-                configs,
-            });
+            self._schedule_config_update(ConfigUpdate::Pairs(configs));
         }
 
         pub fn update_collateral_configs(
             &mut self, 
             configs: Vec<(ResourceAddress, CollateralConfig)>,
         ) {
-            authorize!(self, {
-                Global::<Config>::from(CONFIG_COMPONENT).update_collateral_configs(configs.clone());
-            });
-
-            Runtime::emit_event(EventCollateralConfigUpdates {
-                configs
-            });
+            self._schedule_config_update(ConfigUpdate::Collaterals(configs));
         }
 
         pub fn remove_collateral_config(
             &mut self, 
             resource: ResourceAddress,
+        ) {
+            self._schedule_config_update(ConfigUpdate::CollateralRemoval(resource));
+        }
+
+        pub fn remove_pair_config(
+            &mut self, 
+            pair_id: PairId,
+        ) {
+            self._schedule_config_update(ConfigUpdate::PairRemoval(pair_id));
+        }
+
+        pub fn cancel_config_update(
+            &mut self, 
+            id: u64,
         ) {
             authorize!(self, {
-                Global::<Config>::from(CONFIG_COMPONENT).remove_collateral_config(resource);
+                Global::<Config>::from(CONFIG_COMPONENT).cancel_update(id);
             });
 
-            Runtime::emit_event(EventCollateralConfigRemoval {
-                resource
+            Runtime::emit_event(EventConfigUpdateCancelled {
+                id,
             });
         }
 
@@ -653,6 +707,19 @@ mod exchange_mod {
             pair_ids.into_iter().map(|pair_id| self._pair_details(&config, &pool, &pair_id)).collect()
         }
 
+        pub fn get_pair_funding_preview(
+            &self,
+            pair_ids: Vec<PairId>,
+            prices: HashMap<PairId, Decimal>,
+        ) -> Vec<PairFundingPreview> {
+            let pair_ids_set: HashSet<PairId> = pair_ids.iter().cloned().collect();
+            let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids_set.clone());
+            let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids_set);
+            let oracle = VirtualOracle::from_prices(Global::<Oracle>::from(ORACLE_COMPONENT), prices);
+
+            pair_ids.into_iter().map(|pair_id| self._pair_funding_preview(&config, &mut pool, &oracle, &pair_id)).collect()
+        }
+
         pub fn get_referral_details(
             &self,
             referral_id: NonFungibleLocalId,
@@ -715,6 +782,12 @@ mod exchange_mod {
                 .map(|(k, v)| (k, v.decompress())).collect()
         }
 
+        pub fn get_pending_config_updates(
+            &self,
+        ) -> Vec<(u64, PendingConfigUpdate)> {
+            Global::<Config>::from(CONFIG_COMPONENT).get_pending_updates()
+        }
+
         pub fn get_collaterals(
             &self,
         ) -> Vec<ResourceAddress> {
@@ -797,6 +870,245 @@ mod exchange_mod {
             })
         }
 
+        pub fn add_junior_liquidity(
+            &self,
+            payment: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), HashSet::new());
+                let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), HashSet::new());
+                let lp_token = self._add_junior_liquidity(&config, &mut pool, payment);
+                pool.realize();
+
+                lp_token
+            })
+        }
+
+        pub fn remove_junior_liquidity(
+            &self,
+            lp_token: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), HashSet::new());
+                let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), HashSet::new());
+                let token = self._remove_junior_liquidity(&config, &mut pool, lp_token);
+                pool.realize();
+
+                token
+            })
+        }
+
+        pub fn queue_add_liquidity(
+            &self,
+            payment: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), HashSet::new());
+                self._assert_base_resource(&payment.resource_address());
+
+                let amount = payment.amount();
+                assert!(
+                    amount >= config.exchange_config().lp_deposit_min,
+                    "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_DEPOSIT_AMOUNT_MIN_NOT_MET, amount, config.exchange_config().lp_deposit_min
+                );
+
+                let (receipt, index) = Global::<MarginPool>::from(POOL_COMPONENT).queue_deposit(payment);
+
+                Runtime::emit_event(EventLiquidityDeposit {
+                    index,
+                    status: LiquidityRequestStatus::Queued,
+                    amount,
+                    lp_amount: dec!(0),
+                });
+
+                receipt
+            })
+        }
+
+        pub fn cancel_add_liquidity(
+            &self,
+            receipt: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let (token, index) = Global::<MarginPool>::from(POOL_COMPONENT).cancel_deposit(receipt);
+
+                Runtime::emit_event(EventLiquidityDeposit {
+                    index,
+                    status: LiquidityRequestStatus::Cancelled,
+                    amount: token.amount(),
+                    lp_amount: dec!(0),
+                });
+
+                token
+            })
+        }
+
+        pub fn claim_add_liquidity(
+            &self,
+            receipt: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let (lp_token, index) = Global::<MarginPool>::from(POOL_COMPONENT).claim_deposit(receipt);
+
+                Runtime::emit_event(EventLiquidityDeposit {
+                    index,
+                    status: LiquidityRequestStatus::Claimed,
+                    amount: dec!(0),
+                    lp_amount: lp_token.amount(),
+                });
+
+                lp_token
+            })
+        }
+
+        pub fn queue_remove_liquidity(
+            &self,
+            lp_token: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), HashSet::new());
+                self._assert_lp_resource(&lp_token.resource_address());
+
+                let lp_amount = lp_token.amount();
+                assert!(
+                    lp_amount >= config.exchange_config().lp_withdrawal_min,
+                    "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_WITHDRAWAL_AMOUNT_MIN_NOT_MET, lp_amount, config.exchange_config().lp_withdrawal_min
+                );
+
+                let (receipt, index) = Global::<MarginPool>::from(POOL_COMPONENT).queue_withdrawal(lp_token, 0);
+
+                Runtime::emit_event(EventLiquidityWithdrawal {
+                    index,
+                    status: LiquidityRequestStatus::Queued,
+                    lp_amount,
+                    amount: dec!(0),
+                });
+
+                receipt
+            })
+        }
+
+        pub fn cancel_remove_liquidity(
+            &self,
+            receipt: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let (lp_token, index) = Global::<MarginPool>::from(POOL_COMPONENT).cancel_withdrawal(receipt);
+
+                Runtime::emit_event(EventLiquidityWithdrawal {
+                    index,
+                    status: LiquidityRequestStatus::Cancelled,
+                    lp_amount: lp_token.amount(),
+                    amount: dec!(0),
+                });
+
+                lp_token
+            })
+        }
+
+        pub fn claim_remove_liquidity(
+            &self,
+            receipt: Bucket,
+        ) -> Bucket {
+            authorize!(self, {
+                let (token, index) = Global::<MarginPool>::from(POOL_COMPONENT).claim_withdrawal(receipt);
+
+                Runtime::emit_event(EventLiquidityWithdrawal {
+                    index,
+                    status: LiquidityRequestStatus::Claimed,
+                    lp_amount: dec!(0),
+                    amount: token.amount(),
+                });
+
+                token
+            })
+        }
+
+        pub fn queue_remove_locked_liquidity(
+            &self,
+            lock_receipt: Bucket,
+        ) -> (Bucket, Bucket, Option<Bucket>) {
+            authorize!(self, {
+                let (lp_token, reward, lock_receipt, term) = self._unlock_liquidity(lock_receipt);
+
+                let lp_amount = lp_token.amount();
+                assert!(
+                    lp_amount.is_positive(),
+                    "{}, VALUE:{}, REQUIRED:{}, OP:> |", ERROR_LOCK_NO_LIQUIDITY, lp_amount, dec!(0)
+                );
+                let (receipt, index) = Global::<MarginPool>::from(POOL_COMPONENT).queue_withdrawal(lp_token, term);
+
+                Runtime::emit_event(EventLiquidityWithdrawal {
+                    index,
+                    status: LiquidityRequestStatus::Queued,
+                    lp_amount,
+                    amount: dec!(0),
+                });
+
+                (receipt, reward, lock_receipt)
+            })
+        }
+
+        pub fn lock_liquidity(
+            &self,
+            lp_token: Bucket,
+            term: i64,
+        ) -> Bucket {
+            authorize!(self, {
+                let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), HashSet::new());
+                self._assert_lp_resource(&lp_token.resource_address());
+
+                let boost = config.exchange_config().lock_terms.iter()
+                    .find(|(lock_term, _)| *lock_term == term)
+                    .map(|(_, boost)| *boost)
+                    .expect(ERROR_INVALID_LOCK_TERM);
+
+                let lp_amount = lp_token.amount();
+                let (receipt, index) = Global::<MarginPool>::from(POOL_COMPONENT).lock(lp_token, term, boost);
+
+                Runtime::emit_event(EventLiquidityLock {
+                    index,
+                    lp_amount,
+                    term,
+                    boost,
+                });
+
+                receipt
+            })
+        }
+
+        pub fn claim_liquidity_lock_rewards(
+            &self,
+            lock_proof: Proof,
+        ) -> Bucket {
+            authorize!(self, {
+                let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), HashSet::new());
+                let checked_lock = lock_proof.check_with_message(pool.lock_receipt_resource(), ERROR_INVALID_LOCK_RECEIPT);
+                let index = match checked_lock.as_non_fungible().non_fungible_local_id() {
+                    NonFungibleLocalId::Integer(id) => id.value(),
+                    _ => panic!("{}", ERROR_INVALID_LOCK_RECEIPT),
+                };
+
+                let reward = Global::<MarginPool>::from(POOL_COMPONENT).claim_lock_rewards(index, pool.base_tokens_amount());
+                let token = self._pay_lock_rewards(&mut pool, index, reward, dec!(0));
+
+                pool.realize();
+
+                token
+            })
+        }
+
+        pub fn unlock_liquidity(
+            &self,
+            lock_receipt: Bucket,
+        ) -> (Bucket, Bucket, Option<Bucket>) {
+            authorize!(self, {
+                let (lp_token, reward, lock_receipt, _) = self._unlock_liquidity(lock_receipt);
+
+                (lp_token, reward, lock_receipt)
+            })
+        }
+
         pub fn create_referral_codes(
             &self, 
             referral_proof: Proof,
@@ -929,7 +1241,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._add_collateral(&config, &mut pool, &mut account, tokens);
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
@@ -967,7 +1279,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1017,7 +1329,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1072,7 +1384,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1134,7 +1446,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1159,7 +1471,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1184,7 +1496,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1209,7 +1521,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._add_collateral(&config, &mut pool, &mut account, tokens);
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
@@ -1244,7 +1556,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                     config
@@ -1296,7 +1608,7 @@ mod exchange_mod {
                     pair_ids.insert(pair_id.clone());
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                     config
@@ -1365,7 +1677,7 @@ mod exchange_mod {
                     pair_ids.insert(pair_id.clone());
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                     config
@@ -1477,7 +1789,7 @@ mod exchange_mod {
                     let pair_ids = account.position_ids();
                     let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                     let pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds_no_max_age(pair_ids), None);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds_no_max_age(pair_ids), None);
 
                     self._settle_fee_oath(&config, &pool, &mut account, &oracle, fee_oath);
                 }
@@ -1493,11 +1805,11 @@ mod exchange_mod {
             &self, 
             account: ComponentAddress, 
             index: ListIndex,
-            price_updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>,
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
         ) -> Bucket {
             authorize!(self, {
                 let mut account = VirtualMarginAccount::new(account);
-                let (request, expired) = account.process_request(index);
+                let (request, submission, expired) = account.process_request(index);
                 
                 let mut pair_ids = account.position_ids();
                 if let Request::MarginOrder(request) = &request {
@@ -1507,14 +1819,14 @@ mod exchange_mod {
 
                 if !expired {
                     let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds(&config, pair_ids.clone()), price_updates);
+                    let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds(&config, pair_ids.clone()), price_updates);
 
                     match request {
                         Request::RemoveCollateral(request) => {
-                            self._remove_collateral(&config, &mut pool, &mut account, &oracle, request);
+                            self._remove_collateral(&config, &mut pool, &mut account, &oracle, request, submission);
                         },
                         Request::MarginOrder(request) => {
-                            self._margin_order(&config, &mut pool, &mut account, &oracle, request);
+                            self._margin_order(&config, &mut pool, &mut account, &oracle, request, submission);
                         },
                     };
 
@@ -1533,13 +1845,13 @@ mod exchange_mod {
             account: ComponentAddress, 
             resource: ResourceAddress, 
             payment: Bucket, 
-            price_updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>,
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
         ) -> (Bucket, Bucket) {
             authorize!(self, {
                 let mut account = VirtualMarginAccount::new(account);
                 let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), HashSet::new());
                 let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), HashSet::new());
-                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), HashMap::new(), price_updates);
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, HashMap::new(), price_updates);
 
                 let (token, remainder) = self._swap_debt(&config, &mut pool, &mut account, &oracle, &resource, payment);
     
@@ -1554,14 +1866,14 @@ mod exchange_mod {
             &self,
             account: ComponentAddress,
             payment: Bucket,
-            price_updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>,
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
         ) -> (Vec<Bucket>, Bucket) {
             authorize!(self, {
                 let mut account = VirtualMarginAccount::new(account);
                 let pair_ids = account.position_ids();
                 let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                 let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds(&config, pair_ids.clone()), price_updates);
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds(&config, pair_ids.clone()), price_updates);
 
                 let tokens = self._liquidate(&config, &mut pool, &mut account, &oracle, payment);
 
@@ -1577,7 +1889,7 @@ mod exchange_mod {
             &self,
             account: ComponentAddress,
             receiver: ComponentAddress,
-            price_updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>,
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
         ) -> Bucket {
             authorize!(self, {
                 assert!(
@@ -1592,7 +1904,7 @@ mod exchange_mod {
                 let pair_ids: HashSet<PairId> = account.position_ids().union(&receiver.position_ids()).cloned().collect();
                 let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
                 let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
-                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), self._pair_feeds(&config, pair_ids.clone()), price_updates);
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, self._pair_feeds(&config, pair_ids.clone()), price_updates);
 
                 self._liquidate_to_margin(&config, &mut pool, &mut account, &mut receiver, &oracle);
 
@@ -1609,7 +1921,7 @@ mod exchange_mod {
             &self, 
             account: ComponentAddress, 
             pair_id: PairId, 
-            price_updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>,
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
         ) -> Bucket {
             authorize!(self, {
                 let mut account = VirtualMarginAccount::new(account);
@@ -1619,7 +1931,7 @@ mod exchange_mod {
                 let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
 
                 let pair_feeds = self._pair_feeds(&config, pair_ids.clone());
-                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), pair_feeds, price_updates);
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, pair_feeds, price_updates);
 
                 self._auto_deleverage(&config, &mut pool, &mut account, &oracle, &pair_id);
 
@@ -1631,10 +1943,36 @@ mod exchange_mod {
             })
         }
 
+        pub fn settle_position(
+            &self, 
+            account: ComponentAddress, 
+            pair_id: PairId, 
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
+        ) -> Bucket {
+            authorize!(self, {
+                let mut account = VirtualMarginAccount::new(account);
+                let mut pair_ids = account.position_ids();
+                pair_ids.insert(pair_id.clone());
+                let config = VirtualConfig::new(Global::<Config>::from(CONFIG_COMPONENT), pair_ids.clone());
+                let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
+
+                let pair_feeds = self._pair_feeds(&config, pair_ids.clone());
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, pair_feeds, price_updates);
+
+                self._settle_position(&config, &mut pool, &mut account, &oracle, &pair_id);
+
+                account.realize();
+                pool.realize();
+
+                let reward = ResourceManager::from_address(KEEPER_REWARD_RESOURCE).mint(config.exchange_config().reward_keeper);
+                reward
+            })
+        }
+
         pub fn update_pairs(
             &self, 
             pair_ids: Vec<PairId>,
-            price_updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>,
+            price_updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>,
         ) -> (Bucket, Vec<bool>) {
             authorize!(self, {
                 let pair_ids: HashSet<PairId> = pair_ids.into_iter().collect();
@@ -1642,9 +1980,12 @@ mod exchange_mod {
                 let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), pair_ids.clone());
 
                 let pair_feeds = self._pair_feeds(&config, pair_ids.clone());
-                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), config.collateral_feeds(), pair_feeds, price_updates);
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, pair_feeds, price_updates);
 
                 let rewarded: Vec<bool> = pair_ids.iter().map(|pair_id| {
+                    if matches!(config.pair_config(pair_id).status, PairStatus::Halted | PairStatus::Settled(_)) {
+                        return false;
+                    }
                     self._update_pair(&config, &mut pool, &oracle, pair_id)
                 }).collect();
 
@@ -1657,55 +1998,299 @@ mod exchange_mod {
             })
         }
 
-        // --- INTERNAL METHODS ---
-
-        fn _pair_feeds(
+        pub fn process_liquidity_requests(
             &self,
-            config: &VirtualConfig,
-            pair_ids: HashSet<PairId>,
-        ) -> HashMap<PairId, i64> {
-            pair_ids.into_iter().map(|pair_id| {
-                let pair_config = config.pair_config(&pair_id);
-                (pair_id, pair_config.price_age_max)
-            }).collect()
-        }
+            n: ListIndex,
+            price_updates: (Vec<u8>, OracleSignature, Vec<ListIndex>),
+        ) -> Bucket {
+            authorize!(self, {
+                let config_component = Global::<Config>::from(CONFIG_COMPONENT);
+                let pool_component = Global::<MarginPool>::from(POOL_COMPONENT);
+                let config_pair_ids: HashSet<PairId> = config_component.get_pair_configs(config_component.get_pair_configs_len(), None)
+                    .into_iter().map(|v| v.pair_id).collect();
+                let pair_ids: HashSet<PairId> = pool_component.get_positions(config_pair_ids).into_iter()
+                    .filter(|(_, pool_position)| !(pool_position.oi_long + pool_position.oi_short).is_zero())
+                    .map(|(pair_id, _)| pair_id)
+                    .collect();
+                let config = VirtualConfig::new(config_component, pair_ids.clone());
+                let mut pool = VirtualLiquidityPool::new(pool_component, pair_ids.clone());
 
-        fn _pair_feeds_no_max_age(
-            &self,
-            pair_ids: HashSet<PairId>,
-        ) -> HashMap<PairId, i64> {
-            pair_ids.into_iter().map(|pair_id| {
-                (pair_id, i64::MAX)
-            }).collect()
+                let pair_feeds = self._pair_feeds(&config, pair_ids.clone());
+                let oracle = VirtualOracle::new(Global::<Oracle>::from(ORACLE_COMPONENT), &config, pair_feeds, Some(price_updates));
+
+                let halted_pair_ids = config.halted_pair_ids();
+                for pair_id in pair_ids.iter().filter(|pair_id| !halted_pair_ids.contains(*pair_id)) {
+                    self._update_pair(&config, &mut pool, &oracle, pair_id);
+                }
+
+                let filled = self._fill_liquidity_deposits(&config, &mut pool, &oracle, &pair_ids, n) + 
+                    self._fill_liquidity_withdrawals(&config, &mut pool, &oracle, &pair_ids, n);
+
+                pool.realize();
+
+                let reward_amount = if filled > 0 { config.exchange_config().reward_keeper } else { dec!(0) };
+                let reward = ResourceManager::from_address(KEEPER_REWARD_RESOURCE).mint(reward_amount);
+                reward
+            })
         }
 
-        fn _pool_value(
+        pub fn apply_config_updates(
             &self, 
-            pool: &VirtualLiquidityPool,
-        ) -> Decimal {
-            pool.base_tokens_amount() + 
-            pool.virtual_balance() + 
-            pool.unrealized_pool_funding() +
-            pool.pnl_snap()
+            ids: Vec<u64>,
+        ) {
+            let mut pending_updates: HashMap<u64, ConfigUpdate> = Global::<Config>::from(CONFIG_COMPONENT).get_pending_updates()
+                .into_iter()
+                .map(|(id, pending_update)| (id, pending_update.update))
+                .collect();
+
+            for id in ids.into_iter() {
+                if let Some(update) = pending_updates.remove(&id) {
+                    self._assert_config_update_allowed(&update);
+                }
+                let updates = authorize!(self, {
+                    Global::<Config>::from(CONFIG_COMPONENT).apply_updates(vec![id])
+                });
+
+                for update in updates.into_iter() {
+                    if let ConfigUpdate::PairRemoval(pair_id) = &update {
+                        self._remove_pair_position(pair_id);
+                        self._cancel_pair_config_updates(pair_id);
+                    }
+                    Runtime::emit_event(EventConfigUpdateActivated {
+                        id,
+                    });
+                    self._emit_config_update(update);
+                }
+            }
         }
 
-        fn _skew_ratio(
+        // --- INTERNAL METHODS ---
+
+        fn _schedule_config_update(
             &self,
-            pool: &VirtualLiquidityPool,
-        ) -> Decimal {
-            pool.skew_abs_snap() / self._pool_value(pool).max(dec!(1))
+            update: ConfigUpdate,
+        ) {
+            self._assert_config_update_allowed(&update);
+            let (id, activation) = authorize!(self, {
+                Global::<Config>::from(CONFIG_COMPONENT).schedule_update(update.clone())
+            });
+
+            Runtime::emit_event(EventConfigUpdateScheduled {
+                id,
+                update,
+                activation,
+            });
+
+            if activation.compare(Clock::current_time_rounded_to_seconds(), TimeComparisonOperator::Lte) {
+                self.apply_config_updates(vec![id]);
+            }
         }
 
-        fn _assert_lp_resource(
+        fn _emit_config_update(
             &self,
-            resource: &ResourceAddress,
+            update: ConfigUpdate,
         ) {
-            assert!(
-                *resource == LP_RESOURCE,
-                "{}, VALUE:{}, REQUIRED:{}, OP:== |", ERROR_INVALID_LP_TOKEN, Runtime::bech32_encode_address(*resource), Runtime::bech32_encode_address(LP_RESOURCE)
-            );
+            match update {
+                ConfigUpdate::Exchange(config) => {
+                    Runtime::emit_event(EventExchangeConfigUpdate {
+                        config,
+                    });
+                },
+                ConfigUpdate::Pairs(configs) => {
+                    Runtime::emit_event(EventPairConfigUpdates {
+                        configs,
+                    });
+                },
+                ConfigUpdate::Collaterals(configs) => {
+                    Runtime::emit_event(EventCollateralConfigUpdates {
+                        configs,
+                    });
+                },
+                ConfigUpdate::CollateralRemoval(resource) => {
+                    Runtime::emit_event(EventCollateralConfigRemoval {
+                        resource,
+                    });
+                },
+                ConfigUpdate::PairRemoval(pair_id) => {
+                    Runtime::emit_event(EventPairConfigRemoval {
+                        pair_id,
+                    });
+                },
+            }
+        }
+
+        fn _assert_config_update_allowed(
+            &self,
+            update: &ConfigUpdate,
+        ) {
+            if let ConfigUpdate::PairRemoval(pair_id) = update {
+                let status = Global::<Config>::from(CONFIG_COMPONENT).get_pair_configs_by_ids(HashSet::from([pair_id.clone()]))
+                    .remove(pair_id)
+                    .flatten()
+                    .expect(ERROR_MISSING_PAIR_CONFIG)
+                    .status;
+                assert!(
+                    matches!(status, PairStatus::Halted | PairStatus::Settled(_)),
+                    "{}, VALUE:{:?}, REQUIRED:{:?}, OP:== |", ERROR_PAIR_NOT_HALTED, status, PairStatus::Halted
+                );
+
+                let pool_position = Global::<MarginPool>::from(POOL_COMPONENT).get_position(pair_id.clone());
+                let oi = pool_position.oi_long + pool_position.oi_short;
+                assert!(
+                    oi.is_zero(),
+                    "{}, VALUE:{}, REQUIRED:{}, OP:== |", ERROR_PAIR_OI_NOT_ZERO, oi, dec!(0)
+                );
+            }
+        }
+
+        fn _remove_pair_position(
+            &self,
+            pair_id: &PairId,
+        ) {
+            authorize!(self, {
+                let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), HashSet::from([pair_id.clone()]));
+                pool.remove_position(pair_id);
+                pool.realize();
+            });
+        }
+
+        fn _cancel_pair_config_updates(
+            &self,
+            pair_id: &PairId,
+        ) {
+            let pending_updates = Global::<Config>::from(CONFIG_COMPONENT).get_pending_updates();
+            for (id, pending_update) in pending_updates.into_iter() {
+                if let ConfigUpdate::Pairs(configs) = &pending_update.update {
+                    if configs.iter().any(|config| config.pair_id == *pair_id) {
+                        authorize!(self, {
+                            Global::<Config>::from(CONFIG_COMPONENT).cancel_update(id);
+                        });
+
+                        Runtime::emit_event(EventConfigUpdateCancelled {
+                            id,
+                        });
+                    }
+                }
+            }
+        }
+
+        fn _pair_feeds(
+            &self,
+            config: &VirtualConfig,
+            pair_ids: HashSet<PairId>,
+        ) -> HashMap<PairId, i64> {
+            let mut pair_feeds: HashMap<PairId, i64> = HashMap::new();
+            for pair_id in pair_ids.into_iter() {
+                let pair_config = config.pair_config(&pair_id);
+                let price_age_max = pair_config.price_age_max;
+                if let FundingModel::Premium(premium) = &pair_config.funding_model {
+                    pair_feeds.entry(premium.mark_pair_id.clone())
+                        .and_modify(|max_age| *max_age = (*max_age).min(price_age_max))
+                        .or_insert(price_age_max);
+                }
+                pair_feeds.entry(pair_id)
+                    .and_modify(|max_age| *max_age = (*max_age).min(price_age_max))
+                    .or_insert(price_age_max);
+            }
+            pair_feeds
+        }
+
+        fn _account_pair_ids(
+            &self,
+            account: &VirtualMarginAccount,
+            oracle: &VirtualOracle,
+        ) -> HashSet<PairId> {
+            let mut pair_ids = account.position_ids();
+            pair_ids.extend(account.collateral_amounts().iter()
+                .filter(|(_, amount)| amount.is_positive())
+                .filter_map(|(resource, _)| oracle.resource_pair_id(resource).cloned()));
+            pair_ids
+        }
+
+        fn _pair_feeds_no_max_age(
+            &self,
+            pair_ids: HashSet<PairId>,
+        ) -> HashMap<PairId, i64> {
+            pair_ids.into_iter().map(|pair_id| {
+                (pair_id, i64::MAX)
+            }).collect()
+        }
+
+        fn _pool_value(
+            &self, 
+            pool: &VirtualLiquidityPool,
+        ) -> Decimal {
+            pool.base_tokens_amount() + 
+            pool.virtual_balance() + 
+            pool.unrealized_pool_funding() +
+            pool.pnl_snap()
+        }
+
+        fn _tranche_values(
+            &self,
+            pool: &VirtualLiquidityPool,
+        ) -> (Decimal, Decimal) {
+            let pool_value = self._pool_value(pool).max(dec!(0));
+            let junior_lp_supply = ResourceManager::from_address(JUNIOR_LP_RESOURCE).total_supply().unwrap();
+            if junior_lp_supply.is_zero() {
+                return (pool_value, dec!(0));
+            }
+
+            let senior_value = pool.senior_book().min(pool_value).max(dec!(0));
+            (senior_value, pool_value - senior_value)
+        }
+
+        fn _sync_senior_book(
+            &self,
+            pool: &mut VirtualLiquidityPool,
+        ) {
+            let junior_lp_supply = ResourceManager::from_address(JUNIOR_LP_RESOURCE).total_supply().unwrap();
+            if junior_lp_supply.is_zero() {
+                let pool_value = self._pool_value(pool).max(dec!(0));
+                pool.set_senior_book(pool_value);
+            }
+        }
+
+        fn _senior_book_share(
+            &self,
+            pool: &VirtualLiquidityPool,
+            lp_supply: Decimal,
+            lp_amount: Decimal,
+        ) -> Decimal {
+            if lp_supply.is_zero() {
+                lp_amount
+            } else {
+                pool.senior_book() * lp_amount / lp_supply
+            }
+        }
+
+        fn _skew_ratio(
+            &self,
+            pool: &VirtualLiquidityPool,
+        ) -> Decimal {
+            pool.skew_abs_snap() / self._pool_value(pool).max(dec!(1))
+        }
+
+        fn _assert_lp_resource(
+            &self,
+            resource: &ResourceAddress,
+        ) {
+            assert!(
+                *resource == LP_RESOURCE,
+                "{}, VALUE:{}, REQUIRED:{}, OP:== |", ERROR_INVALID_LP_TOKEN, Runtime::bech32_encode_address(*resource), Runtime::bech32_encode_address(LP_RESOURCE)
+            );
         }
         
+        fn _assert_junior_lp_resource(
+            &self,
+            resource: &ResourceAddress,
+        ) {
+            assert!(
+                *resource == JUNIOR_LP_RESOURCE,
+                "{}, VALUE:{}, REQUIRED:{}, OP:== |", ERROR_INVALID_JUNIOR_LP_TOKEN, Runtime::bech32_encode_address(*resource), Runtime::bech32_encode_address(JUNIOR_LP_RESOURCE)
+            );
+        }
+
         fn _assert_base_resource(
             &self,
             resource: &ResourceAddress,
@@ -1716,6 +2301,20 @@ mod exchange_mod {
             );
         }
 
+        fn _assert_pairs_not_halted(
+            &self,
+            config: &VirtualConfig,
+            account: &VirtualMarginAccount,
+        ) {
+            for pair_id in account.positions().keys() {
+                let status = &config.pair_config(pair_id).status;
+                assert!(
+                    *status != PairStatus::Halted,
+                    "{}, VALUE:{:?}, REQUIRED:{:?}, OP:!= |", ERROR_PAIR_DISABLED, status, PairStatus::Halted
+                );
+            }
+        }
+
         fn _assert_pool_integrity(
             &self,
             config: &VirtualConfig,
@@ -1773,6 +2372,54 @@ mod exchange_mod {
             );
         }
 
+        fn _oi_value_cap(
+            &self,
+            pair_config: &PairConfig,
+            pool_value: Decimal,
+            ratio: Decimal,
+        ) -> Decimal {
+            let oi_value_cap = (pool_value.max(dec!(0)) * ratio).max(pair_config.oi_value_floor);
+            if pair_config.oi_value_ceiling.is_positive() {
+                oi_value_cap.min(pair_config.oi_value_ceiling)
+            } else {
+                oi_value_cap
+            }
+        }
+
+        fn _assert_oi_side_value(
+            &self,
+            pair_config: &PairConfig,
+            pool_value: Decimal,
+            oi_value: Decimal,
+        ) {
+            if pair_config.oi_side_ratio_max.is_zero() {
+                return;
+            }
+
+            let oi_value_cap = self._oi_value_cap(pair_config, pool_value, pair_config.oi_side_ratio_max);
+            assert!(
+                oi_value <= oi_value_cap,
+                "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_PAIR_OI_TOO_HIGH, oi_value, oi_value_cap
+            );
+        }
+
+        fn _assert_oi_combined_value(
+            &self,
+            pair_config: &PairConfig,
+            pool_value: Decimal,
+            oi_value: Decimal,
+        ) {
+            if pair_config.oi_combined_ratio_max.is_zero() {
+                return;
+            }
+
+            let oi_value_cap = self._oi_value_cap(pair_config, pool_value, pair_config.oi_combined_ratio_max);
+            assert!(
+                oi_value <= oi_value_cap,
+                "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_PAIR_OI_TOO_HIGH, oi_value, oi_value_cap
+            );
+        }
+
         fn _assert_collaterals_limit(
             &self,
             config: &VirtualConfig,
@@ -1812,13 +2459,86 @@ mod exchange_mod {
             let skew = (pool_position.oi_long - pool_position.oi_short) * price;
 
             let fee_0 = value_abs * pair_config.fee_0;
-            let fee_1 = value * (dec!(2) * skew + value) * pair_config.fee_1;
+            let fee_1 = match &pair_config.fee_model {
+                FeeModel::LinearSkew => value * (dec!(2) * skew + value) * pair_config.fee_1,
+                FeeModel::PiecewiseLinear(points) => {
+                    self._fee_cost_piecewise_linear(points, skew + value) - self._fee_cost_piecewise_linear(points, skew)
+                },
+                FeeModel::Sqrt(coefficient) => {
+                    self._fee_cost_sqrt(*coefficient, skew + value) - self._fee_cost_sqrt(*coefficient, skew)
+                },
+            };
             let fee_max = value_abs * exchange_config.fee_max;
             let fee = ((fee_0 + fee_1) * fee_rebate).clamp(dec!(0), fee_max);
             
             fee
         }
 
+        fn _fee_cost_piecewise_linear(
+            &self,
+            points: &[(Decimal, Decimal)],
+            skew: Decimal,
+        ) -> Decimal {
+            let skew_abs = skew.checked_abs().expect(ERROR_ARITHMETIC);
+
+            let mut cost = dec!(0);
+            for (i, &(skew_0, rate_0)) in points.iter().enumerate() {
+                if skew_abs <= skew_0 {
+                    break;
+                }
+                cost += match points.get(i + 1) {
+                    Some(&(skew_1, rate_1)) if skew_abs < skew_1 => {
+                        let rate = rate_0 + (rate_1 - rate_0) * (skew_abs - skew_0) / (skew_1 - skew_0);
+                        (rate_0 + rate) / dec!(2) * (skew_abs - skew_0)
+                    },
+                    Some(&(skew_1, rate_1)) => (rate_0 + rate_1) / dec!(2) * (skew_1 - skew_0),
+                    None => rate_0 * (skew_abs - skew_0),
+                };
+            }
+
+            cost
+        }
+
+        fn _fee_cost_sqrt(
+            &self,
+            coefficient: Decimal,
+            skew: Decimal,
+        ) -> Decimal {
+            let skew_abs = skew.checked_abs().expect(ERROR_ARITHMETIC);
+            let skew_sqrt = skew_abs.checked_sqrt().expect(ERROR_ARITHMETIC);
+
+            coefficient * dec!(2) / dec!(3) * skew_abs * skew_sqrt
+        }
+
+        fn _skew_rebate(
+            &self,
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            pair_id: &PairId,
+            skew_0: Decimal,
+            skew_1: Decimal,
+            trade_value: Decimal,
+        ) -> Decimal {
+            let exchange_config = config.exchange_config();
+            let pair_config = config.pair_config(pair_id);
+            let skew_reduction = skew_0.checked_abs().expect(ERROR_ARITHMETIC) - skew_1.checked_abs().expect(ERROR_ARITHMETIC);
+            if !skew_reduction.is_positive() || pair_config.skew_rebate_rate.is_zero() || exchange_config.rebate_budget.is_zero() {
+                return dec!(0);
+            }
+
+            let current_time = Clock::current_time_rounded_to_seconds();
+            let period_seconds = current_time.seconds_since_unix_epoch - pool.rebate_period_start().seconds_since_unix_epoch;
+            if period_seconds >= exchange_config.rebate_period_seconds {
+                pool.reset_rebate_period(current_time);
+            }
+
+            let budget_remaining = (exchange_config.rebate_budget - pool.rebate_spent()).max(dec!(0));
+            let rebate = (skew_reduction.min(trade_value) * pair_config.skew_rebate_rate).min(budget_remaining);
+            pool.add_rebate_spent(rebate);
+
+            rebate
+        }
+
         fn _account_details(
             &self,
             config: &VirtualConfig,
@@ -1836,14 +2556,17 @@ mod exchange_mod {
                 } else {
                     -position.amount * (pool_position.funding_short_index - position.funding_index)            
                 };
-                let margin_initial = position.amount.checked_abs().expect(ERROR_ARITHMETIC) * pair_config.margin_initial;
-                let margin_maintenance = position.amount.checked_abs().expect(ERROR_ARITHMETIC) * pair_config.margin_maintenance;
+                let amount_abs = position.amount.checked_abs().expect(ERROR_ARITHMETIC);
+                let (margin_tier, margin_initial, margin_maintenance) = pair_config.margin_tier(amount_abs * pool_position.mark_price);
+                let margin_initial = amount_abs * margin_initial;
+                let margin_maintenance = amount_abs * margin_maintenance;
 
                 PositionDetails {
                     pair_id: pair_id.clone(),
                     amount: position.amount,
                     cost: position.cost,
                     funding,
+                    margin_tier,
                     margin_initial,
                     margin_maintenance,
                 }
@@ -1905,13 +2628,18 @@ mod exchange_mod {
             config: &VirtualConfig,
             pool: &VirtualLiquidityPool,
         ) -> PoolDetails {
-            let lp_token_manager = ResourceManager::from_address(LP_RESOURCE);
-            let lp_supply = lp_token_manager.total_supply().unwrap();
-            let pool_value = self._pool_value(pool).max(dec!(1));
+            let lp_supply = ResourceManager::from_address(LP_RESOURCE).total_supply().unwrap();
+            let junior_lp_supply = ResourceManager::from_address(JUNIOR_LP_RESOURCE).total_supply().unwrap();
+            let (senior_value, junior_value) = self._tranche_values(pool);
             let lp_price = if lp_supply.is_zero() {
                 dec!(1)
             } else {
-                pool_value / lp_supply
+                senior_value.max(dec!(1)) / lp_supply
+            };
+            let junior_lp_price = if junior_lp_supply.is_zero() {
+                dec!(1)
+            } else {
+                junior_value.max(dec!(1)) / junior_lp_supply
             };
 
             PoolDetails {
@@ -1923,6 +2651,19 @@ mod exchange_mod {
                 skew_ratio_cap: config.exchange_config().skew_ratio_cap,
                 lp_supply,
                 lp_price,
+                senior_value,
+                senior_book: pool.senior_book(),
+                junior_value,
+                junior_lp_supply,
+                junior_lp_price,
+                deposits_start: pool.deposits_start(),
+                deposits_len: pool.deposits_len(),
+                deposit_receipt_resource: pool.deposit_receipt_resource(),
+                withdrawals_start: pool.withdrawals_start(),
+                withdrawals_len: pool.withdrawals_len(),
+                withdrawal_receipt_resource: pool.withdrawal_receipt_resource(),
+                lock_weight: pool.lock_weight(),
+                lock_receipt_resource: pool.lock_receipt_resource(),
             }
         }
 
@@ -1971,21 +2712,16 @@ mod exchange_mod {
         ) -> Bucket {
             let lp_token_manager = ResourceManager::from_address(LP_RESOURCE);
             self._assert_base_resource(&payment.resource_address());
+            self._sync_senior_book(pool);
 
             let value = payment.amount();
-            let fee = value * config.exchange_config().fee_liquidity_add;
-            let pool_value = self._pool_value(pool).max(dec!(1));
+            let (senior_value, _) = self._tranche_values(pool);
             let lp_supply = lp_token_manager.total_supply().unwrap();
-
-            let (lp_amount, lp_price) = if lp_supply.is_zero() {
-                (value - fee, dec!(1))
-            } else {
-                let lp_price = pool_value / lp_supply;
-                let lp_amount = (value - fee) / lp_price;
-                (lp_amount, lp_price)
-            };
+            let (lp_price, lp_amount, fee) = self._add_liquidity_value(config, &lp_token_manager, senior_value, value);
+            let senior_book = self._senior_book_share(pool, lp_supply, lp_amount);
 
             pool.deposit(payment);
+            pool.add_senior_book(senior_book);
             let lp_token = lp_token_manager.mint(lp_amount);
             let (fee_pool, fee_protocol, fee_treasury) = self._settle_fees_basic(config, pool, fee);
 
@@ -2009,15 +2745,13 @@ mod exchange_mod {
         ) -> Bucket {
             let lp_token_manager = ResourceManager::from_address(LP_RESOURCE);
             self._assert_lp_resource(&lp_token.resource_address());
+            self._sync_senior_book(pool);
 
             let lp_amount = lp_token.amount();
-            let pool_value = self._pool_value(pool).max(dec!(0));
+            let (senior_value, _) = self._tranche_values(pool);
             let lp_supply = lp_token_manager.total_supply().unwrap();
-
-            let lp_price = pool_value / lp_supply;
-            let value = lp_amount * lp_price;
-            let fee = value * config.exchange_config().fee_liquidity_remove;
-            let withdraw_amount = value - fee;
+            let (lp_price, withdraw_amount, fee) = self._remove_liquidity_value(config, &lp_token_manager, senior_value, lp_amount);
+            let senior_book = self._senior_book_share(pool, lp_supply, lp_amount);
 
             assert!(
                 withdraw_amount <= pool.base_tokens_amount(),
@@ -2025,6 +2759,7 @@ mod exchange_mod {
             );
             
             lp_token.burn();
+            pool.add_senior_book(-senior_book);
             let token = pool.withdraw(withdraw_amount, TO_ZERO);
             let (fee_pool, fee_protocol, fee_treasury) = self._settle_fees_basic(config, pool, fee);
             
@@ -2042,6 +2777,259 @@ mod exchange_mod {
             token
         }
 
+        fn _add_junior_liquidity(
+            &self,
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            payment: Bucket,
+        ) -> Bucket {
+            let lp_token_manager = ResourceManager::from_address(JUNIOR_LP_RESOURCE);
+            self._assert_base_resource(&payment.resource_address());
+            self._sync_senior_book(pool);
+
+            let value = payment.amount();
+            let (_, junior_value) = self._tranche_values(pool);
+            let (lp_price, lp_amount, fee) = self._add_liquidity_value(config, &lp_token_manager, junior_value, value);
+
+            pool.deposit(payment);
+            let lp_token = lp_token_manager.mint(lp_amount);
+            let (fee_pool, fee_protocol, fee_treasury) = self._settle_fees_basic(config, pool, fee);
+
+            Runtime::emit_event(EventJuniorLiquidityChange {
+                lp_price,
+                lp_amount,
+                amount: value,
+                fee_pool,
+                fee_protocol,
+                fee_treasury,
+            });
+
+            lp_token
+        }
+
+        fn _remove_junior_liquidity(
+            &self,
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            lp_token: Bucket,
+        ) -> Bucket {
+            let lp_token_manager = ResourceManager::from_address(JUNIOR_LP_RESOURCE);
+            self._assert_junior_lp_resource(&lp_token.resource_address());
+            self._sync_senior_book(pool);
+
+            let lp_amount = lp_token.amount();
+            let (_, junior_value) = self._tranche_values(pool);
+            let (lp_price, withdraw_amount, fee) = self._remove_liquidity_value(config, &lp_token_manager, junior_value, lp_amount);
+
+            assert!(
+                withdraw_amount <= pool.base_tokens_amount(),
+                "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_WITHDRAWAL_INSUFFICIENT_POOL_TOKENS, withdraw_amount, pool.base_tokens_amount()
+            );
+
+            lp_token.burn();
+            let token = pool.withdraw(withdraw_amount, TO_ZERO);
+            let (fee_pool, fee_protocol, fee_treasury) = self._settle_fees_basic(config, pool, fee);
+            self._sync_senior_book(pool);
+
+            self._assert_pool_integrity(config, pool, dec!(0));
+
+            Runtime::emit_event(EventJuniorLiquidityChange {
+                lp_price,
+                lp_amount: -lp_amount,
+                amount: -token.amount(),
+                fee_pool,
+                fee_protocol,
+                fee_treasury,
+            });
+
+            token
+        }
+
+        fn _add_liquidity_value(
+            &self,
+            config: &VirtualConfig,
+            lp_token_manager: &ResourceManager,
+            tranche_value: Decimal,
+            value: Decimal,
+        ) -> (Decimal, Decimal, Decimal) {
+            let fee = value * config.exchange_config().fee_liquidity_add;
+            let tranche_value = tranche_value.max(dec!(1));
+            let lp_supply = lp_token_manager.total_supply().unwrap();
+
+            let (lp_amount, lp_price) = if lp_supply.is_zero() {
+                (value - fee, dec!(1))
+            } else {
+                let lp_price = tranche_value / lp_supply;
+                let lp_amount = (value - fee) / lp_price;
+                (lp_amount, lp_price)
+            };
+
+            (lp_price, lp_amount, fee)
+        }
+
+        fn _remove_liquidity_value(
+            &self,
+            config: &VirtualConfig,
+            lp_token_manager: &ResourceManager,
+            tranche_value: Decimal,
+            lp_amount: Decimal,
+        ) -> (Decimal, Decimal, Decimal) {
+            let tranche_value = tranche_value.max(dec!(0));
+            let lp_supply = lp_token_manager.total_supply().unwrap();
+
+            let lp_price = tranche_value / lp_supply;
+            let value = lp_amount * lp_price;
+            let fee = value * config.exchange_config().fee_liquidity_remove;
+            let withdraw_amount = value - fee;
+
+            (lp_price, withdraw_amount, fee)
+        }
+
+        fn _fill_liquidity_deposits(
+            &self,
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            oracle: &VirtualOracle,
+            pair_ids: &HashSet<PairId>,
+            n: ListIndex,
+        ) -> ListIndex {
+            let lp_token_manager = ResourceManager::from_address(LP_RESOURCE);
+            self._sync_senior_book(pool);
+
+            let mut filled = 0;
+            for (index, deposit) in pool.deposits(n) {
+                if deposit.status != LiquidityRequestStatus::Queued {
+                    continue;
+                }
+                // The queue is ordered by submission, later deposits wait for newer prices
+                if !oracle.is_published_after(pair_ids, deposit.submission) {
+                    break;
+                }
+
+                let amount = deposit.amount;
+                let (senior_value, _) = self._tranche_values(pool);
+                let lp_supply = lp_token_manager.total_supply().unwrap();
+                let (lp_price, lp_amount, fee) = self._add_liquidity_value(config, &lp_token_manager, senior_value, amount);
+                let senior_book = self._senior_book_share(pool, lp_supply, lp_amount);
+
+                let lp_token = lp_token_manager.mint(lp_amount);
+                let token = pool.fill_deposit(index, lp_token);
+                pool.deposit(token);
+                pool.add_senior_book(senior_book);
+                let (fee_pool, fee_protocol, fee_treasury) = self._settle_fees_basic(config, pool, fee);
+
+                Runtime::emit_event(EventLiquidityChange {
+                    lp_price,
+                    lp_amount,
+                    amount,
+                    fee_pool,
+                    fee_protocol,
+                    fee_treasury,
+                });
+                Runtime::emit_event(EventLiquidityDeposit {
+                    index,
+                    status: LiquidityRequestStatus::Filled,
+                    amount,
+                    lp_amount,
+                });
+
+                filled += 1;
+            }
+
+            filled
+        }
+
+        fn _unlock_liquidity(
+            &self,
+            lock_receipt: Bucket,
+        ) -> (Bucket, Bucket, Option<Bucket>, i64) {
+            let mut pool = VirtualLiquidityPool::new(Global::<MarginPool>::from(POOL_COMPONENT), HashSet::new());
+            let (lp_token, lock_receipt, reward, term, index) = Global::<MarginPool>::from(POOL_COMPONENT).unlock(lock_receipt, pool.base_tokens_amount());
+
+            let token = self._pay_lock_rewards(&mut pool, index, reward, lp_token.amount());
+            pool.realize();
+
+            (lp_token, token, lock_receipt, term)
+        }
+
+        fn _pay_lock_rewards(
+            &self,
+            pool: &mut VirtualLiquidityPool,
+            index: ListIndex,
+            amount: Decimal,
+            lp_amount: Decimal,
+        ) -> Bucket {
+            pool.add_virtual_balance(amount);
+            let token = pool.withdraw(amount, TO_ZERO);
+
+            Runtime::emit_event(EventLiquidityLockClaim {
+                index,
+                amount: token.amount(),
+                lp_amount,
+            });
+
+            token
+        }
+
+        fn _fill_liquidity_withdrawals(
+            &self,
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            oracle: &VirtualOracle,
+            pair_ids: &HashSet<PairId>,
+            n: ListIndex,
+        ) -> ListIndex {
+            let lp_token_manager = ResourceManager::from_address(LP_RESOURCE);
+            self._sync_senior_book(pool);
+
+            // The queue is ordered by submission, withdrawals from the first one submitted after the prices wait for
+            // newer prices
+            let mut withdrawals: Vec<(ListIndex, LpWithdrawal)> = pool.withdrawals(n).into_iter()
+                .take_while(|(_, withdrawal)| oracle.is_published_after(pair_ids, withdrawal.submission))
+                .filter(|(_, withdrawal)| withdrawal.status == LiquidityRequestStatus::Queued)
+                .collect();
+            withdrawals.sort_by(|(_, a), (_, b)| b.priority.cmp(&a.priority));
+
+            let mut filled = 0;
+            for (index, withdrawal) in withdrawals {
+                let lp_amount = withdrawal.lp_amount;
+                let (senior_value, _) = self._tranche_values(pool);
+                let lp_supply = lp_token_manager.total_supply().unwrap();
+                let (lp_price, withdraw_amount, fee) = self._remove_liquidity_value(config, &lp_token_manager, senior_value, lp_amount);
+                if withdraw_amount > pool.base_tokens_amount() {
+                    break;
+                }
+                let senior_book = self._senior_book_share(pool, lp_supply, lp_amount);
+
+                let token = pool.withdraw(withdraw_amount, TO_ZERO);
+                let amount = token.amount();
+                pool.fill_withdrawal(index, token).burn();
+                pool.add_senior_book(-senior_book);
+                let (fee_pool, fee_protocol, fee_treasury) = self._settle_fees_basic(config, pool, fee);
+
+                Runtime::emit_event(EventLiquidityChange {
+                    lp_price,
+                    lp_amount: -lp_amount,
+                    amount: -amount,
+                    fee_pool,
+                    fee_protocol,
+                    fee_treasury,
+                });
+                Runtime::emit_event(EventLiquidityWithdrawal {
+                    index,
+                    status: LiquidityRequestStatus::Filled,
+                    lp_amount,
+                    amount,
+                });
+
+                filled += 1;
+            }
+
+            self._assert_pool_integrity(config, pool, dec!(0));
+
+            filled
+        }
+
         fn _set_level_1_auth(
             &self,
             account: &mut VirtualMarginAccount,
@@ -2183,7 +3171,11 @@ mod exchange_mod {
             account: &mut VirtualMarginAccount, 
             oracle: &VirtualOracle,
             request: RequestRemoveCollateral,
+            submission: Instant,
         ) {
+            oracle.assert_published_after(&self._account_pair_ids(account, oracle), submission);
+            self._assert_pairs_not_halted(config, account);
+
             let target_account_component = request.target_account;
             let mut claims: Vec<(ResourceAddress, Decimal)> = request.claims.iter()
                 .fold(HashMap::new(), |mut claims, (resource, amount)| {
@@ -2239,6 +3231,7 @@ mod exchange_mod {
             account: &mut VirtualMarginAccount,
             oracle: &VirtualOracle,
             request: RequestMarginOrder,
+            submission: Instant,
         ) {
             let pair_id = &request.pair_id;
             let amount = request.amount;
@@ -2248,12 +3241,29 @@ mod exchange_mod {
             let activate_requests = request.activate_requests;
             let cancel_requests = request.cancel_requests;
 
-            let price = oracle.price(pair_id);
+            let mut pair_ids = self._account_pair_ids(account, oracle);
+            pair_ids.insert(pair_id.clone());
+            oracle.assert_published_after(&pair_ids, submission);
+
+            let status = &config.pair_config(pair_id).status;
             assert!(
-                price_limit.compare(price),
-                "{}, VALUE:{}, REQUIRED:{}, OP:{} |", ERROR_MARGIN_ORDER_PRICE_LIMIT, price, price_limit.price(), price_limit.op()
+                matches!(status, PairStatus::Active | PairStatus::ReduceOnly),
+                "{}, VALUE:{:?}, REQUIRED:{:?}, OP:== |", ERROR_PAIR_DISABLED, status, PairStatus::Active
             );
 
+            let price = oracle.price_fill(pair_id, amount);
+            let price = if price_limit.compare(price) {
+                price
+            } else {
+                let current_time = Clock::current_time_rounded_to_seconds();
+                let traded_through = oracle.traded_through(pair_id, amount, price_limit, submission, current_time);
+                assert!(
+                    traded_through,
+                    "{}, VALUE:{}, REQUIRED:{}, OP:{} |", ERROR_MARGIN_ORDER_PRICE_LIMIT, price, price_limit.price(), price_limit.op()
+                );
+                price_limit.price()
+            };
+
             self._update_pair(config, pool, oracle, pair_id);
             let funding = self._settle_funding(pool, account, pair_id);
                 
@@ -2276,18 +3286,29 @@ mod exchange_mod {
 
                 (amount_close, amount_open)
             };
+            if *status == PairStatus::ReduceOnly {
+                assert!(
+                    amount_open.is_zero(),
+                    "{}, VALUE:{}, REQUIRED:{}, OP:== |", ERROR_PAIR_REDUCE_ONLY, amount_open, dec!(0)
+                );
+            }
 
             let skew_0 = pool.skew_abs_snap();
+            let price_index = oracle.price(pair_id);
+            let pair_skew_0 = {
+                let pool_position = pool.position(pair_id);
+                (pool_position.oi_long - pool_position.oi_short) * price_index
+            };
 
             let mut pnl = dec!(0);
             let mut fee_paid = dec!(0);
             if !amount_close.is_zero() {
-                let (pnl_close, fee_close) = self._close_position(config, pool, account, oracle, pair_id, amount_close);
+                let (pnl_close, fee_close) = self._close_position(config, pool, account, oracle, pair_id, amount_close, price);
                 pnl += pnl_close;
                 fee_paid += fee_close;
             }
             if !amount_open.is_zero() {
-                let fee_open = self._open_position(config, pool, account, oracle, pair_id, amount_open);
+                let fee_open = self._open_position(config, pool, account, oracle, pair_id, amount_open, price);
                 fee_paid += fee_open;
             }
             
@@ -2299,6 +3320,13 @@ mod exchange_mod {
 
             let (fee_pool, fee_protocol, fee_treasury, fee_referral) = self._settle_fees_referral(config, pool, account, fee_paid);
 
+            let pair_skew_1 = {
+                let pool_position = pool.position(pair_id);
+                (pool_position.oi_long - pool_position.oi_short) * price_index
+            };
+            let rebate = self._skew_rebate(config, pool, pair_id, pair_skew_0, pair_skew_1, trade_value);
+            self._settle_account(pool, account, rebate);
+
             let activated_requests = account.try_set_keeper_requests_status(activate_requests, STATUS_ACTIVE);
             let cancelled_requests = account.try_set_keeper_requests_status(cancel_requests, STATUS_CANCELLED);
             
@@ -2320,6 +3348,7 @@ mod exchange_mod {
                 fee_protocol,
                 fee_treasury,
                 fee_referral,
+                rebate,
             });
         }
 
@@ -2382,7 +3411,7 @@ mod exchange_mod {
             let result_collateral = self._liquidate_collateral(config, account, oracle); 
             
             let virtual_balance = account.virtual_balance();
-            let account_value = result_positions.pnl + result_collateral.collateral_value_discounted + virtual_balance;
+            let account_value = result_positions.pnl_mark + result_collateral.collateral_value_discounted + virtual_balance;
             let margin = result_positions.margin_positions + result_collateral.margin_collateral;
 
             assert!(
@@ -2448,7 +3477,7 @@ mod exchange_mod {
             let result_collateral = self._liquidate_collateral(config, account, oracle); 
             
             let virtual_balance = account.virtual_balance();
-            let account_value = result_positions.pnl + result_collateral.collateral_value_discounted + virtual_balance;
+            let account_value = result_positions.pnl_mark + result_collateral.collateral_value_discounted + virtual_balance;
             let margin = result_positions.margin_positions + result_collateral.margin_collateral;
 
             assert!(
@@ -2517,6 +3546,12 @@ mod exchange_mod {
         ) {
             let exchange_config = config.exchange_config();
 
+            let status = &config.pair_config(pair_id).status;
+            assert!(
+                matches!(status, PairStatus::Active | PairStatus::ReduceOnly),
+                "{}, VALUE:{:?}, REQUIRED:{:?}, OP:== |", ERROR_PAIR_DISABLED, status, PairStatus::Active
+            );
+
             self._update_pair(config, pool, oracle, pair_id);
             let funding = self._settle_funding(pool, account, pair_id);
 
@@ -2548,7 +3583,7 @@ mod exchange_mod {
             );
             
             let amount_close = -amount;
-            let (pnl, fee_paid) = self._close_position(config, pool, account, oracle, pair_id, amount_close);
+            let (pnl, fee_paid) = self._close_position(config, pool, account, oracle, pair_id, amount_close, oracle.price_fill(pair_id, amount_close));
             let (fee_pool, fee_protocol, fee_treasury, fee_referral) = self._settle_fees_referral(config, pool, account, fee_paid);
 
             let skew_ratio_1 = self._skew_ratio(pool);
@@ -2573,6 +3608,47 @@ mod exchange_mod {
             });
         }
 
+        fn _settle_position(
+            &self, 
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            account: &mut VirtualMarginAccount, 
+            oracle: &VirtualOracle,
+            pair_id: &PairId, 
+        ) {
+            let status = &config.pair_config(pair_id).status;
+            let price = match status {
+                PairStatus::Settled(price) => *price,
+                _ => panic!("{}, VALUE:{:?}, REQUIRED:Settled, OP:== |", ERROR_PAIR_NOT_SETTLED, status),
+            };
+
+            self._update_pair(config, pool, oracle, pair_id);
+            let funding = self._settle_funding(pool, account, pair_id);
+
+            let amount = account.position(pair_id).amount;
+            assert!(
+                !amount.is_zero(),
+                "{}, VALUE:{}, REQUIRED:{}, OP:!= |", ERROR_SETTLE_NO_POSITION, amount, dec!(0)
+            );
+
+            let amount_close = -amount;
+            let (pnl, fee_paid) = self._close_position(config, pool, account, oracle, pair_id, amount_close, price);
+            let (fee_pool, fee_protocol, fee_treasury, fee_referral) = self._settle_fees_referral(config, pool, account, fee_paid);
+
+            Runtime::emit_event(EventSettlePosition {
+                account: account.address(),
+                pair_id: pair_id.clone(),
+                price,
+                amount_close,
+                pnl: pnl + funding,
+                funding,
+                fee_pool,
+                fee_protocol,
+                fee_treasury,
+                fee_referral,
+            });
+        }
+
         fn _open_position(
             &self,
             config: &VirtualConfig,
@@ -2581,13 +3657,14 @@ mod exchange_mod {
             oracle: &VirtualOracle,
             pair_id: &PairId, 
             amount: Decimal, 
+            price: Decimal,
         ) -> Decimal {
             let exchange_config = config.exchange_config();
             let pair_config = config.pair_config(pair_id);
             let fee_rebate = account.fee_rebate();
 
-            let price = oracle.price(pair_id);
             let value = amount * price;
+            let pool_value = self._pool_value(pool);
 
             let pool_position = pool.position_mut(pair_id);
             let position = account.position_mut(pair_id);
@@ -2603,13 +3680,16 @@ mod exchange_mod {
                     pool_position.oi_long <= pair_config.oi_max, 
                     "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_PAIR_OI_TOO_HIGH, pool_position.oi_long, pair_config.oi_max
                 );
+                self._assert_oi_side_value(pair_config, pool_value, pool_position.oi_long * price);
             } else {
                 pool_position.oi_short -= amount;
                 assert!(
                     pool_position.oi_short <= pair_config.oi_max , 
                     "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_PAIR_OI_TOO_HIGH, pool_position.oi_short, pair_config.oi_max
                 );
+                self._assert_oi_side_value(pair_config, pool_value, pool_position.oi_short * price);
             }
+            self._assert_oi_combined_value(pair_config, pool_value, (pool_position.oi_long + pool_position.oi_short) * price);
             pool_position.cost += cost;
             
             position.amount += amount;
@@ -2636,12 +3716,12 @@ mod exchange_mod {
             oracle: &VirtualOracle,
             pair_id: &PairId, 
             amount: Decimal, 
+            price: Decimal,
         ) -> (Decimal, Decimal) {
             let exchange_config = config.exchange_config();
             let pair_config = config.pair_config(pair_id);
             let fee_rebate = account.fee_rebate();
 
-            let price = oracle.price(pair_id);
             let value = amount * price;
 
             let pool_position = pool.position_mut(pair_id);
@@ -2693,11 +3773,21 @@ mod exchange_mod {
             let pnl_snap_delta = pnl - pool_position.pnl_snap;
             pool_position.pnl_snap = pnl;
             
+            // Funding and the update time of a settled pair stay frozen at the settlement
+            if let PairStatus::Settled(_) = pair_config.status {
+                pool_position.mark_price = price;
+                pool_position.last_price = price;
+                pool.add_skew_abs_snap(skew_abs_snap_delta);
+                pool.add_pnl_snap(pnl_snap_delta);
+                return false;
+            }
+
             let current_time = Clock::current_time_rounded_to_seconds();
             let period_seconds = current_time.seconds_since_unix_epoch - pool_position.last_update.seconds_since_unix_epoch;
             let period = Decimal::from(period_seconds) / dec!(31536000); // 1 year
             
             let price_delta_ratio = (price - pool_position.last_price).checked_abs().expect(ERROR_ARITHMETIC) / pool_position.last_price;
+            pool_position.mark_price = self._mark_price(pair_config, pool_position, price);
             pool_position.last_price = price;
             pool_position.last_update = current_time;
             
@@ -2741,9 +3831,7 @@ mod exchange_mod {
                 }
 
                 if !oi_long.is_zero() && !oi_short.is_zero() {
-                    let funding_1_rate = skew * pair_config.funding_1;
-                    let funding_2_rate = pool_position.funding_2_rate.clamp(funding_2_min, funding_2_max) * pair_config.funding_2;
-                    let funding_rate = funding_1_rate + funding_2_rate;
+                    let (funding_rate, funding_pool_rate) = self._funding_rates(pair_config, pool_position, oracle, price);
 
                     let (funding_long_index, funding_short_index, funding_share) = if funding_rate.is_positive() {
                         let funding_long = funding_rate * period;
@@ -2763,10 +3851,6 @@ mod exchange_mod {
                         (funding_long_index, funding_short_index, funding_share)
                     };
 
-                    let funding_pool_0_rate = (oi_long + oi_short) * price * pair_config.funding_pool_0;
-                    let funding_pool_1_rate = skew_abs * pair_config.funding_pool_1;
-                    let funding_pool_rate = funding_pool_0_rate + funding_pool_1_rate;
-
                     let funding_pool = funding_pool_rate * period;
                     let funding_pool_index = funding_pool / (oi_long + oi_short);
 
@@ -2792,14 +3876,124 @@ mod exchange_mod {
             }
         }
 
+        fn _funding_rates(
+            &self,
+            pair_config: &PairConfig,
+            pool_position: &PoolPosition,
+            oracle: &VirtualOracle,
+            price: Decimal,
+        ) -> (Decimal, Decimal) {
+            let oi_long = pool_position.oi_long;
+            let oi_short = pool_position.oi_short;
+            let skew = (oi_long - oi_short) * price;
+            let skew_abs = skew.checked_abs().expect(ERROR_ARITHMETIC);
+
+            let funding_rate = match &pair_config.funding_model {
+                FundingModel::Skew => {
+                    let funding_2_max = oi_long * price;
+                    let funding_2_min = -oi_short * price;
+                    let funding_1_rate = skew * pair_config.funding_1;
+                    let funding_2_rate = pool_position.funding_2_rate.clamp(funding_2_min, funding_2_max) * pair_config.funding_2;
+                    funding_1_rate + funding_2_rate
+                },
+                FundingModel::Premium(premium) => {
+                    let premium_rate = self._premium_rate(premium, oracle.price(&premium.mark_pair_id), price);
+                    if premium_rate.is_positive() {
+                        premium_rate * oi_long * price
+                    } else {
+                        premium_rate * oi_short * price
+                    }
+                },
+            };
+
+            let funding_pool_0_rate = (oi_long + oi_short) * price * pair_config.funding_pool_0;
+            let funding_pool_1_rate = skew_abs * pair_config.funding_pool_1;
+            let funding_pool_rate = funding_pool_0_rate + funding_pool_1_rate;
+
+            (funding_rate, funding_pool_rate)
+        }
+
+        fn _premium_rate(
+            &self,
+            premium: &PremiumFunding,
+            mark_price: Decimal,
+            price: Decimal,
+        ) -> Decimal {
+            let premium_index = (mark_price - price) / price;
+            let premium_dampened = if premium_index > premium.premium_dampener {
+                premium_index - premium.premium_dampener
+            } else if premium_index < -premium.premium_dampener {
+                premium_index + premium.premium_dampener
+            } else {
+                dec!(0)
+            };
+
+            premium_dampened.clamp(-premium.premium_max, premium.premium_max) * premium.funding_premium
+        }
+
+        fn _pair_funding_preview(
+            &self,
+            config: &VirtualConfig,
+            pool: &mut VirtualLiquidityPool,
+            oracle: &VirtualOracle,
+            pair_id: &PairId,
+        ) -> PairFundingPreview {
+            let pair_config = config.pair_config(pair_id);
+            let price = oracle.price(pair_id);
+
+            self._update_pair(config, pool, oracle, pair_id);
+            let pool_position = pool.position(pair_id);
+            let oi_long = pool_position.oi_long;
+            let oi_short = pool_position.oi_short;
+
+            let (funding_long_rate, funding_short_rate, funding_pool_rate) = if !oi_long.is_zero() && !oi_short.is_zero() {
+                let (funding_rate, funding_pool_rate) = self._funding_rates(pair_config, pool_position, oracle, price);
+                let funding_share_rate = funding_rate.checked_abs().expect(ERROR_ARITHMETIC) * pair_config.funding_share;
+
+                let (funding_long, funding_short) = if funding_rate.is_positive() {
+                    (funding_rate, -(funding_rate - funding_share_rate))
+                } else {
+                    (-(-funding_rate - funding_share_rate), -funding_rate)
+                };
+                let oi_value = (oi_long + oi_short) * price;
+                let funding_pool_ratio = funding_pool_rate / oi_value;
+
+                (
+                    funding_long / (oi_long * price) + funding_pool_ratio,
+                    funding_short / (oi_short * price) + funding_pool_ratio,
+                    (funding_pool_rate + funding_share_rate) / oi_value,
+                )
+            } else {
+                (dec!(0), dec!(0), dec!(0))
+            };
+
+            PairFundingPreview {
+                pair_id: pair_id.clone(),
+                price,
+                funding_long_rate,
+                funding_short_rate,
+                funding_pool_rate,
+                funding_2_rate: pool_position.funding_2_rate,
+                funding_long_index: pool_position.funding_long_index,
+                funding_short_index: pool_position.funding_short_index,
+            }
+        }
+
         fn _update_pair_snaps(
             &self, 
             pool: &mut VirtualLiquidityPool,
             oracle: &VirtualOracle,
             pair_id: &PairId,
         ) {
-            let price = oracle.price(pair_id);
+            self._update_pair_snaps_at_price(pool, pair_id, oracle.price(pair_id));
+        }
 
+        fn _update_pair_snaps_at_price(
+            &self, 
+            pool: &mut VirtualLiquidityPool,
+            pair_id: &PairId,
+            price: Decimal,
+        ) {
             let pool_position = pool.position_mut(pair_id);
             let oi_long = pool_position.oi_long;
             let oi_short = pool_position.oi_short;
@@ -2817,6 +4011,50 @@ mod exchange_mod {
             pool.add_pnl_snap(pnl_snap_delta);
         }
 
+        fn _mark_price(
+            &self,
+            pair_config: &PairConfig,
+            pool_position: &PoolPosition,
+            price: Decimal,
+        ) -> Decimal {
+            if pair_config.mark_ema_seconds == 0 || pool_position.mark_price.is_zero() {
+                return price;
+            }
+
+            let current_time = Clock::current_time_rounded_to_seconds();
+            let period_seconds = current_time.seconds_since_unix_epoch - pool_position.last_update.seconds_since_unix_epoch;
+            let periods = Decimal::from(period_seconds) / Decimal::from(pair_config.mark_ema_seconds);
+            let weight = dec!(1) - self._exp_neg(periods);
+            pool_position.mark_price + (price - pool_position.mark_price) * weight
+        }
+
+        fn _exp_neg(
+            &self,
+            x: Decimal,
+        ) -> Decimal {
+            const EXP_NEG_ONE: Decimal = dec!(0.367879441171442322);
+            if x >= dec!(42) {
+                return dec!(0);
+            }
+
+            let whole = x.checked_round(0, RoundingMode::ToZero).expect(ERROR_ARITHMETIC);
+            let fraction = x - whole;
+            let mut term = dec!(1);
+            let mut result = dec!(1);
+            for i in 1..20 {
+                term = term * -fraction / Decimal::from(i);
+                result += term;
+            }
+
+            let mut remaining = whole;
+            while remaining >= dec!(1) {
+                result = result * EXP_NEG_ONE;
+                remaining -= dec!(1);
+            }
+
+            result
+        }
+
         fn _value_positions(
             &self,
             config: &VirtualConfig,
@@ -2831,13 +4069,16 @@ mod exchange_mod {
             let mut total_margin = dec!(0);
             for (pair_id, position) in account.positions().iter() {
                 let pair_config = config.pair_config(pair_id);
-                let price = oracle.price(pair_id);
+                let pool_position = pool.position(pair_id);
+                let price = if pair_config.status == PairStatus::Halted {
+                    pool_position.mark_price
+                } else {
+                    self._mark_price(pair_config, pool_position, oracle.price(pair_id))
+                };
                 let amount = position.amount;
                 let value = -amount * price;
                 let value_abs = value.checked_abs().expect(ERROR_ARITHMETIC);
 
-                let pool_position = pool.position(pair_id);
-
                 let fee = self._calculate_fee(exchange_config, pair_config, pool_position, fee_rebate, price, value);
                 let cost = position.cost;
                 let funding = if amount.is_positive() {
@@ -2847,7 +4088,8 @@ mod exchange_mod {
                 };
 
                 let pnl = -value - cost - fee - funding;
-                let margin = value_abs * pair_config.margin_initial;
+                let (_, margin_initial, _) = pair_config.margin_tier(value_abs);
+                let margin = value_abs * margin_initial;
                 total_pnl += pnl;
                 total_margin += margin;
             }
@@ -2868,12 +4110,18 @@ mod exchange_mod {
             let exchange_config = config.exchange_config();
             let fee_rebate = account.fee_rebate();
 
+            // Halted pairs are not updated, their positions are closed at the last mark price
             let pair_ids: Vec<PairId> = account.positions().keys().cloned().collect();
-            pair_ids.iter().for_each(|pair_id| {
+            let halted_pair_ids: HashSet<PairId> = pair_ids.iter()
+                .filter(|pair_id| config.pair_config(pair_id).status == PairStatus::Halted)
+                .cloned()
+                .collect();
+            pair_ids.iter().filter(|pair_id| !halted_pair_ids.contains(*pair_id)).for_each(|pair_id| {
                 self._update_pair(config, pool, oracle, pair_id);
             });
             
             let mut total_pnl = dec!(0);
+            let mut total_pnl_mark = dec!(0);
             let mut total_margin = dec!(0);
             let mut total_funding = dec!(0);
             let mut total_fee_paid = dec!(0);
@@ -2881,14 +4129,20 @@ mod exchange_mod {
             let mut prices = vec![];
             for (pair_id, position) in account.positions_mut() {
                 let pair_config = config.pair_config(pair_id);
-                let price = oracle.price(pair_id);
+                let pool_position = pool.position_mut(pair_id);
+                let mark_price = pool_position.mark_price;
+                let price = if halted_pair_ids.contains(pair_id) {
+                    mark_price
+                } else {
+                    oracle.price(pair_id)
+                };
                 let amount = position.amount;
                 let value = -amount * price;
-                let value_abs = value.checked_abs().expect(ERROR_ARITHMETIC);
-
-                let pool_position = pool.position_mut(pair_id);
+                let value_mark = -amount * mark_price;
+                let value_mark_abs = value_mark.checked_abs().expect(ERROR_ARITHMETIC);
 
                 let fee = self._calculate_fee(exchange_config, pair_config, pool_position, fee_rebate, price, value);
+                let fee_mark = self._calculate_fee(exchange_config, pair_config, pool_position, fee_rebate, mark_price, value_mark);
                 let cost = position.cost;
                 let funding = if amount.is_positive() {
                     pool_position.oi_long -= amount;
@@ -2899,11 +4153,15 @@ mod exchange_mod {
                 };
                 pool_position.cost -= cost;
 
+                // Eligibility is decided at the mark price, the positions are closed at the index price
                 let pnl = -value - cost - fee - funding;
-                let margin = value_abs * pair_config.margin_maintenance;
+                let pnl_mark = -value_mark - cost - fee_mark - funding;
+                let (_, _, margin_maintenance) = pair_config.margin_tier(value_mark_abs);
+                let margin = value_mark_abs * margin_maintenance;
                 total_funding += funding;
                 
                 total_pnl += pnl;
+                total_pnl_mark += pnl_mark;
                 total_margin += margin;
                 total_fee_paid += fee;
                 position_amounts.push((pair_id.clone(), amount));
@@ -2913,11 +4171,17 @@ mod exchange_mod {
             }
             pool.add_unrealized_pool_funding(-total_funding);
             pair_ids.iter().for_each(|pair_id| {
-                self._update_pair_snaps(pool, oracle, pair_id);
+                if halted_pair_ids.contains(pair_id) {
+                    let price = pool.position(pair_id).mark_price;
+                    self._update_pair_snaps_at_price(pool, pair_id, price);
+                } else {
+                    self._update_pair_snaps(pool, oracle, pair_id);
+                }
             });
 
             ResultLiquidatePositions {
                 pnl: total_pnl,
+                pnl_mark: total_pnl_mark,
                 margin_positions: total_margin,
                 funding_paid: -total_funding,
                 fee_paid: total_fee_paid,
@@ -2936,7 +4200,7 @@ mod exchange_mod {
             let mut total_margin = dec!(0);
             for (&resource, &amount) in account.collateral_amounts().iter() {
                 if let Some(collateral_config) = config.collateral_configs().get(&resource) {
-                    let price_resource = oracle.price_resource(resource);
+                    let price_resource = oracle.price_resource_bid(resource);
                     let value = amount * price_resource;
                     let value_discounted = value * collateral_config.discount;
                     let margin = value * collateral_config.margin;
@@ -2965,7 +4229,7 @@ mod exchange_mod {
             let mut prices = vec![];
             for (&resource, &amount) in account.collateral_amounts().iter() {
                 if let Some(collateral_config) = config.collateral_configs().get(&resource) {
-                    let price_resource = oracle.price_resource(resource);
+                    let price_resource = oracle.price_resource_bid(resource);
                     let value = amount * price_resource;
                     let value_discounted = value * collateral_config.discount;
                     let margin = value * collateral_config.margin;
@@ -3024,6 +4288,35 @@ mod exchange_mod {
             -funding
         }
 
+        fn _fee_lock(
+            &self,
+            config: &VirtualConfig,
+            pool: &VirtualLiquidityPool,
+            fee_pool: Decimal,
+        ) -> Decimal {
+            if pool.lock_weight().is_positive() && fee_pool.is_positive() {
+                fee_pool * config.exchange_config().fee_share_lock
+            } else {
+                dec!(0)
+            }
+        }
+
+        fn _fee_senior(
+            &self,
+            config: &VirtualConfig,
+            pool: &VirtualLiquidityPool,
+            fee_pool: Decimal,
+        ) -> Decimal {
+            let junior_lp_supply = ResourceManager::from_address(JUNIOR_LP_RESOURCE).total_supply().unwrap();
+            let (senior_value, junior_value) = self._tranche_values(pool);
+            let pool_value = senior_value + junior_value;
+            if junior_lp_supply.is_positive() && pool_value.is_positive() && fee_pool.is_positive() {
+                fee_pool * senior_value / pool_value * config.exchange_config().fee_share_senior
+            } else {
+                dec!(0)
+            }
+        }
+
         fn _settle_fees_basic(
             &self,
             config: &VirtualConfig,
@@ -3033,9 +4326,13 @@ mod exchange_mod {
             let exchange_config = config.exchange_config();
             let fee_protocol = fee_paid * exchange_config.fee_share_protocol;
             let fee_treasury = fee_paid * exchange_config.fee_share_treasury;
-            let fee_pool = fee_paid - fee_protocol - fee_treasury;
+            let fee_lock = self._fee_lock(config, pool, fee_paid - fee_protocol - fee_treasury);
+            let fee_pool = fee_paid - fee_protocol - fee_treasury - fee_lock;
 
-            pool.add_virtual_balance(-fee_protocol -fee_treasury);
+            pool.add_virtual_balance(-fee_protocol -fee_treasury -fee_lock);
+            pool.add_lock_rewards(fee_lock);
+            let fee_senior = self._fee_senior(config, pool, fee_pool);
+            pool.add_senior_book(fee_senior);
             Global::<FeeDistributor>::from(FEE_DISTRIBUTOR_COMPONENT).distribute(fee_protocol, fee_treasury);
 
             (-fee_pool, -fee_protocol, -fee_treasury)
@@ -3052,9 +4349,13 @@ mod exchange_mod {
             let fee_protocol = fee_paid * exchange_config.fee_share_protocol;
             let fee_treasury = fee_paid * exchange_config.fee_share_treasury;
             let fee_referral = fee_paid * exchange_config.fee_share_referral * account.fee_share_referral();
-            let fee_pool = fee_paid - fee_protocol - fee_treasury - fee_referral;
+            let fee_lock = self._fee_lock(config, pool, fee_paid - fee_protocol - fee_treasury - fee_referral);
+            let fee_pool = fee_paid - fee_protocol - fee_treasury - fee_referral - fee_lock;
 
-            pool.add_virtual_balance(-fee_protocol -fee_treasury -fee_referral);
+            pool.add_virtual_balance(-fee_protocol -fee_treasury -fee_referral -fee_lock);
+            pool.add_lock_rewards(fee_lock);
+            let fee_senior = self._fee_senior(config, pool, fee_pool);
+            pool.add_senior_book(fee_senior);
             Global::<FeeDistributor>::from(FEE_DISTRIBUTOR_COMPONENT).distribute(fee_protocol, fee_treasury);
             account.reward_referral(fee_referral);
 
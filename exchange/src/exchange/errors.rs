@@ -6,6 +6,11 @@ pub const ERROR_PAIR_OI_TOO_HIGH: &str = "Pair OI exceeds maximum";
 pub const ERROR_INSUFFICIENT_MARGIN: &str = "Insufficient margin";
 
 pub const ERROR_PAIR_DISABLED: &str = "Pair disabled";
+pub const ERROR_PAIR_REDUCE_ONLY: &str = "Pair is reduce only";
+pub const ERROR_PAIR_NOT_SETTLED: &str = "Pair not settled";
+pub const ERROR_SETTLE_NO_POSITION: &str = "No position to settle";
+pub const ERROR_PAIR_OI_NOT_ZERO: &str = "Pair has open interest";
+pub const ERROR_PAIR_NOT_HALTED: &str = "Pair not halted or settled";
 
 pub const ERROR_MARGIN_ORDER_PRICE_LIMIT: &str = "Price limit not met";
 pub const ERROR_MARGIN_ORDER_SLIPPAGE_LIMIT: &str = "Slippage limit exceeded";
@@ -13,8 +18,14 @@ pub const ERROR_MARGIN_ORDER_SLIPPAGE_LIMIT: &str = "Slippage limit exceeded";
 pub const ERROR_INVALID_PAYMENT_TOKEN: &str = "Invalid payment token";
 pub const ERROR_INVALID_PROTOCOL_TOKEN: &str = "Invalid protocol token";
 pub const ERROR_INVALID_LP_TOKEN: &str = "Invalid LP token";
+pub const ERROR_INVALID_JUNIOR_LP_TOKEN: &str = "Invalid junior LP token";
+pub const ERROR_INVALID_LOCK_TERM: &str = "Invalid lock term";
+pub const ERROR_INVALID_LOCK_RECEIPT: &str = "Invalid lock receipt";
+pub const ERROR_LOCK_NO_LIQUIDITY: &str = "Lock has no liquidity left";
 
 pub const ERROR_INSUFFICIENT_PAYMENT: &str = "Insufficient payment amount";
+pub const ERROR_DEPOSIT_AMOUNT_MIN_NOT_MET: &str = "Deposit amount minimum not met";
+pub const ERROR_WITHDRAWAL_AMOUNT_MIN_NOT_MET: &str = "Withdrawal amount minimum not met";
 pub const ERROR_WITHDRAWAL_INSUFFICIENT_BALANCE: &str = "Insufficient balance for withdrawal";
 pub const ERROR_WITHDRAWAL_INSUFFICIENT_POOL_TOKENS: &str = "Insufficient pool balance for withdrawal";
 
@@ -44,6 +55,8 @@ pub const ERROR_INVALID_AUTH_LEVEL: &str = "Invalid authorization level";
 
 pub const ERROR_INVALID_PRICE: &str = "Invalid price";
 pub const ERROR_PRICE_TOO_OLD: &str = "Price too old";
+pub const ERROR_PRICE_DISAGREEMENT: &str = "Oracle prices disagree";
+pub const ERROR_PRICE_BEFORE_SUBMISSION: &str = "Price published before request submission";
 
 pub const ERROR_MISSING_POOL_POSITION: &str = "Pool position not found";
 pub const ERROR_MISSING_PAIR_CONFIG: &str = "Pair config not found";
@@ -1,7 +1,7 @@
 use scrypto::prelude::*;
 use account::KeeperRequest;
 use common::{PairId, ListIndex};
-use pool::PoolPosition;
+use pool::{PoolPosition, LiquidityRequestStatus};
 use ::config::*;
 use super::requests::PriceLimit;
 
@@ -30,6 +30,28 @@ pub struct EventCollateralConfigRemoval {
     pub resource: ResourceAddress,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventPairConfigRemoval {
+    pub pair_id: PairId,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventConfigUpdateScheduled {
+    pub id: u64,
+    pub update: ConfigUpdate,
+    pub activation: Instant,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventConfigUpdateActivated {
+    pub id: u64,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventConfigUpdateCancelled {
+    pub id: u64,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
 pub struct EventPairUpdates {
     pub updates: Vec<(PairId, PoolPosition)>,
@@ -65,6 +87,47 @@ pub struct EventLiquidityChange {
     pub fee_treasury: Decimal,
 }
 
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventJuniorLiquidityChange {
+    pub lp_price: Decimal,
+    pub lp_amount: Decimal,
+    pub amount: Decimal,
+    pub fee_pool: Decimal,
+    pub fee_protocol: Decimal,
+    pub fee_treasury: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventLiquidityDeposit {
+    pub index: ListIndex,
+    pub status: LiquidityRequestStatus,
+    pub amount: Decimal,
+    pub lp_amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventLiquidityWithdrawal {
+    pub index: ListIndex,
+    pub status: LiquidityRequestStatus,
+    pub lp_amount: Decimal,
+    pub amount: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventLiquidityLock {
+    pub index: ListIndex,
+    pub lp_amount: Decimal,
+    pub term: i64,
+    pub boost: Decimal,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventLiquidityLockClaim {
+    pub index: ListIndex,
+    pub amount: Decimal,
+    pub lp_amount: Decimal,
+}
+
 #[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
 pub struct EventAddCollateral {
     pub account: ComponentAddress,
@@ -92,6 +155,7 @@ pub struct EventMarginOrder {
     pub fee_protocol: Decimal,
     pub fee_treasury: Decimal,
     pub fee_referral: Decimal,
+    pub rebate: Decimal,
     pub activated_requests: Vec<ListIndex>,
     pub cancelled_requests: Vec<ListIndex>,
 }
@@ -150,3 +214,17 @@ pub struct EventAutoDeleverage {
     pub pnl_percent: Decimal,
     pub threshold: Decimal,
 }
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventSettlePosition {
+    pub account: ComponentAddress,
+    pub pair_id: PairId,
+    pub price: Decimal,
+    pub amount_close: Decimal,
+    pub pnl: Decimal,
+    pub funding: Decimal,
+    pub fee_pool: Decimal,
+    pub fee_protocol: Decimal,
+    pub fee_treasury: Decimal,
+    pub fee_referral: Decimal,
+}
@@ -17,6 +17,7 @@ pub struct RecoveryKeyData {
 pub struct PositionDetails {
     pub pair_id: PairId,
     pub amount: Decimal,
+    pub margin_tier: u8,
     pub margin_initial: Decimal,
     pub margin_maintenance: Decimal,
     pub cost: Decimal,
@@ -63,6 +64,19 @@ pub struct PoolDetails {
     pub skew_ratio_cap: Decimal,
     pub lp_supply: Decimal,
     pub lp_price: Decimal,
+    pub senior_value: Decimal,
+    pub senior_book: Decimal,
+    pub junior_value: Decimal,
+    pub junior_lp_supply: Decimal,
+    pub junior_lp_price: Decimal,
+    pub deposits_start: ListIndex,
+    pub deposits_len: ListIndex,
+    pub deposit_receipt_resource: ResourceAddress,
+    pub withdrawals_start: ListIndex,
+    pub withdrawals_len: ListIndex,
+    pub withdrawal_receipt_resource: ResourceAddress,
+    pub lock_weight: Decimal,
+    pub lock_receipt_resource: ResourceAddress,
 }
 
 #[derive(ScryptoSbor, Clone, Debug)]
@@ -72,6 +86,18 @@ pub struct PairDetails {
     pub pair_config: PairConfig,
 }
 
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PairFundingPreview {
+    pub pair_id: PairId,
+    pub price: Decimal,
+    pub funding_long_rate: Decimal,
+    pub funding_short_rate: Decimal,
+    pub funding_pool_rate: Decimal,
+    pub funding_2_rate: Decimal,
+    pub funding_long_index: Decimal,
+    pub funding_short_index: Decimal,
+}
+
 #[derive(ScryptoSbor, Clone, Debug)]
 pub struct ReferralDetails {
     pub referral: ReferralData,
@@ -91,6 +117,7 @@ pub struct ResultValuePositions {
 
 pub struct ResultLiquidatePositions {
     pub pnl: Decimal,
+    pub pnl_mark: Decimal,
     pub margin_positions: Decimal,
     pub funding_paid: Decimal,
     pub fee_paid: Decimal,
@@ -36,6 +36,26 @@ impl VirtualConfig {
         }
     }
 
+    pub fn price_tolerance(&self, pair_id: &PairId) -> Decimal {
+        self.pair_configs.get(pair_id).map_or(self.exchange_config.price_tolerance, |config| config.price_tolerance)
+    }
+
+    /// Final settlement prices of the loaded pairs that are settled
+    pub fn settlement_prices(&self) -> HashMap<PairId, Decimal> {
+        self.pair_configs.iter().filter_map(|(pair_id, config)| match config.status {
+            PairStatus::Settled(price) => Some((pair_id.clone(), price)),
+            _ => None,
+        }).collect()
+    }
+
+    /// Ids of the loaded pairs that are halted
+    pub fn halted_pair_ids(&self) -> HashSet<PairId> {
+        self.pair_configs.iter()
+            .filter(|(_, config)| config.status == PairStatus::Halted)
+            .map(|(pair_id, _)| pair_id.clone())
+            .collect()
+    }
+
     pub fn collateral_configs(&self) -> &HashMap<ResourceAddress, CollateralConfig> {
         &self.collateral_configs
     }
@@ -326,7 +326,7 @@ impl VirtualMarginAccount {
         }
     }
 
-    pub fn process_request(&mut self, index: ListIndex) -> (Request, bool) {
+    pub fn process_request(&mut self, index: ListIndex) -> (Request, Instant, bool) {
         assert!(
             index >= self.valid_requests_start(),
             "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_PROCESS_REQUEST_BEFORE_VALID_START, index, self.valid_requests_start()
@@ -363,7 +363,7 @@ impl VirtualMarginAccount {
         self._remove_active_request(index);
         self.request_updates.insert(index, keeper_request);
 
-        (request, expired)
+        (request, submission, expired)
     }
 
     pub fn deposit_collateral_batch(&mut self, tokens: Vec<Bucket>) {
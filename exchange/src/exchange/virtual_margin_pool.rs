@@ -1,6 +1,6 @@
 use scrypto::prelude::*;
 use pool::*;
-use common::PairId;
+use common::{PairId, ListIndex};
 use super::errors::*;
 use super::events::*;
 use super::exchange_mod::MarginPool;
@@ -9,11 +9,24 @@ pub struct VirtualLiquidityPool {
     pool: Global<MarginPool>,
     positions: HashMap<PairId, PoolPosition>,
     position_updates: HashSet<PairId>,
+    position_removals: HashSet<PairId>,
     base_tokens_amount: Decimal,
     virtual_balance: Decimal,
     unrealized_pool_funding: Decimal,
     skew_abs_snap: Decimal,
     pnl_snap: Decimal,
+    senior_book: Decimal,
+    rebate_period_start: Instant,
+    rebate_spent: Decimal,
+    deposits_start: ListIndex,
+    deposits_len: ListIndex,
+    deposit_receipt_resource: ResourceAddress,
+    withdrawals_start: ListIndex,
+    withdrawals_len: ListIndex,
+    withdrawal_receipt_resource: ResourceAddress,
+    lock_weight: Decimal,
+    lock_receipt_resource: ResourceAddress,
+    lock_rewards: Decimal,
 }
 
 impl VirtualLiquidityPool {
@@ -24,11 +37,24 @@ impl VirtualLiquidityPool {
             pool,
             positions: pool_info.positions,
             position_updates: HashSet::new(),
+            position_removals: HashSet::new(),
             base_tokens_amount: pool_info.base_tokens_amount,
             virtual_balance: pool_info.virtual_balance,
             unrealized_pool_funding: pool_info.unrealized_pool_funding,
             skew_abs_snap: pool_info.skew_abs_snap,
             pnl_snap: pool_info.pnl_snap,
+            senior_book: pool_info.senior_book,
+            rebate_period_start: pool_info.rebate_period_start,
+            rebate_spent: pool_info.rebate_spent,
+            deposits_start: pool_info.deposits_start,
+            deposits_len: pool_info.deposits_len,
+            deposit_receipt_resource: pool_info.deposit_receipt_resource,
+            withdrawals_start: pool_info.withdrawals_start,
+            withdrawals_len: pool_info.withdrawals_len,
+            withdrawal_receipt_resource: pool_info.withdrawal_receipt_resource,
+            lock_weight: pool_info.lock_weight,
+            lock_receipt_resource: pool_info.lock_receipt_resource,
+            lock_rewards: dec!(0),
         }
     }
 
@@ -39,6 +65,10 @@ impl VirtualLiquidityPool {
             unrealized_pool_funding: self.unrealized_pool_funding,
             skew_abs_snap: self.skew_abs_snap,
             pnl_snap: self.pnl_snap,
+            senior_book: self.senior_book,
+            rebate_period_start: self.rebate_period_start,
+            rebate_spent: self.rebate_spent,
+            lock_rewards: self.lock_rewards,
         };
 
         let updates: Vec<(PairId, PoolPosition)> = pool_updates.position_updates.iter()
@@ -51,6 +81,9 @@ impl VirtualLiquidityPool {
         }
 
         self.pool.update(pool_updates);
+        for pair_id in self.position_removals.into_iter() {
+            self.pool.remove_position(pair_id);
+        }
     }
 
     pub fn position(&self, pair_id: &PairId) -> &PoolPosition {
@@ -62,6 +95,15 @@ impl VirtualLiquidityPool {
         self.positions.get_mut(pair_id).expect(ERROR_MISSING_POOL_POSITION)
     }
 
+    pub fn remove_position(&mut self, pair_id: &PairId) {
+        let position = self.positions.remove(pair_id).expect(ERROR_MISSING_POOL_POSITION);
+        self.position_updates.remove(pair_id);
+        self.position_removals.insert(pair_id.clone());
+        self.skew_abs_snap -= position.skew_abs_snap;
+        self.pnl_snap -= position.pnl_snap;
+        self.virtual_balance += position.pnl_snap;
+    }
+
     pub fn base_tokens_amount(&self) -> Decimal {
         self.base_tokens_amount
     }
@@ -82,6 +124,58 @@ impl VirtualLiquidityPool {
         self.pnl_snap
     }
 
+    pub fn senior_book(&self) -> Decimal {
+        self.senior_book
+    }
+
+    pub fn rebate_period_start(&self) -> Instant {
+        self.rebate_period_start
+    }
+
+    pub fn rebate_spent(&self) -> Decimal {
+        self.rebate_spent
+    }
+
+    pub fn deposits_start(&self) -> ListIndex {
+        self.deposits_start
+    }
+
+    pub fn deposits_len(&self) -> ListIndex {
+        self.deposits_len
+    }
+
+    pub fn deposit_receipt_resource(&self) -> ResourceAddress {
+        self.deposit_receipt_resource
+    }
+
+    pub fn withdrawals_start(&self) -> ListIndex {
+        self.withdrawals_start
+    }
+
+    pub fn withdrawals_len(&self) -> ListIndex {
+        self.withdrawals_len
+    }
+
+    pub fn withdrawal_receipt_resource(&self) -> ResourceAddress {
+        self.withdrawal_receipt_resource
+    }
+
+    pub fn deposits(&self, n: ListIndex) -> Vec<(ListIndex, LpDeposit)> {
+        self.pool.get_deposits(n, Some(self.deposits_start))
+    }
+
+    pub fn lock_weight(&self) -> Decimal {
+        self.lock_weight
+    }
+
+    pub fn lock_receipt_resource(&self) -> ResourceAddress {
+        self.lock_receipt_resource
+    }
+
+    pub fn withdrawals(&self, n: ListIndex) -> Vec<(ListIndex, LpWithdrawal)> {
+        self.pool.get_withdrawals(n, Some(self.withdrawals_start))
+    }
+
     pub fn deposit(&mut self, token: Bucket) {
         self.base_tokens_amount += token.amount();
         self.pool.deposit(token);
@@ -93,6 +187,14 @@ impl VirtualLiquidityPool {
         token
     }
 
+    pub fn fill_deposit(&mut self, index: ListIndex, lp_token: Bucket) -> Bucket {
+        self.pool.fill_deposit(index, lp_token)
+    }
+
+    pub fn fill_withdrawal(&mut self, index: ListIndex, token: Bucket) -> Bucket {
+        self.pool.fill_withdrawal(index, token)
+    }
+
     pub fn add_virtual_balance(&mut self, virtual_balance: Decimal) {
         self.virtual_balance += virtual_balance;
     }
@@ -108,4 +210,25 @@ impl VirtualLiquidityPool {
     pub fn add_pnl_snap(&mut self, pnl_snap: Decimal) {
         self.pnl_snap += pnl_snap;
     }
-}
\ No newline at end of file
+
+    pub fn set_senior_book(&mut self, senior_book: Decimal) {
+        self.senior_book = senior_book;
+    }
+
+    pub fn add_senior_book(&mut self, senior_book: Decimal) {
+        self.senior_book += senior_book;
+    }
+
+    pub fn reset_rebate_period(&mut self, rebate_period_start: Instant) {
+        self.rebate_period_start = rebate_period_start;
+        self.rebate_spent = dec!(0);
+    }
+
+    pub fn add_rebate_spent(&mut self, rebate_spent: Decimal) {
+        self.rebate_spent += rebate_spent;
+    }
+
+    pub fn add_lock_rewards(&mut self, lock_rewards: Decimal) {
+        self.lock_rewards += lock_rewards;
+    }
+}
@@ -1,15 +1,26 @@
 use scrypto::prelude::*;
 use common::{PairId, ListIndex};
+use oracle::OracleSignature;
 use super::errors::*;
+use super::requests::PriceLimit;
+use super::virtual_config::VirtualConfig;
 use super::exchange_mod::Oracle;
 
 pub struct VirtualOracle {
+    oracles: Vec<Global<Oracle>>,
     prices: HashMap<PairId, Decimal>,
+    confidences: HashMap<PairId, Decimal>,
+    timestamps: HashMap<PairId, Instant>,
     resource_map: HashMap<ResourceAddress, PairId>,
 }
 
 impl VirtualOracle {
-    pub fn new(oracle: Global<Oracle>, resource_feeds: HashMap<ResourceAddress, (PairId, i64)>, mut pair_feeds: HashMap<PairId, i64>, updates: Option<(Vec<u8>, Bls12381G2Signature, ListIndex)>) -> Self {
+    pub fn new(oracle: Global<Oracle>, config: &VirtualConfig, mut pair_feeds: HashMap<PairId, i64>, updates: Option<(Vec<u8>, OracleSignature, Vec<ListIndex>)>) -> Self {
+        let settlement_prices = config.settlement_prices();
+        let halted_pair_ids = config.halted_pair_ids();
+        pair_feeds.retain(|pair_id, _| !settlement_prices.contains_key(pair_id) && !halted_pair_ids.contains(pair_id));
+
+        let resource_feeds = config.collateral_feeds();
         for (_, (pair_id, resource_max_age)) in resource_feeds.iter() {
             pair_feeds.entry(pair_id.clone()).and_modify(|max_age| {
                 if *resource_max_age < *max_age {
@@ -19,41 +30,167 @@ impl VirtualOracle {
         }
         
         let pair_ids: HashSet<PairId> = pair_feeds.keys().cloned().collect();
-        let prices_with_timestamp = if let Some((update_data, update_signature, key_id)) = updates {
-            oracle.push_and_get_prices_with_auth(pair_ids, update_data, update_signature, key_id)
+        let mut oracle_prices = vec![if let Some((update_data, update_signature, key_ids)) = updates {
+            oracle.push_and_get_prices_with_auth(pair_ids.clone(), update_data, update_signature, key_ids)
         } else {
-            oracle.get_prices_with_auth(pair_ids)
-        };
+            oracle.get_prices_with_auth(pair_ids.clone())
+        }];
+        let mut oracles = vec![oracle];
+        for oracle_component in config.exchange_config().oracle_components.iter() {
+            let oracle = Global::<Oracle>::from(*oracle_component);
+            oracle_prices.push(oracle.get_prices_with_auth(pair_ids.clone()));
+            oracles.push(oracle);
+        }
 
         let current_time = Clock::current_time_rounded_to_seconds();
-        let prices = prices_with_timestamp.into_iter().map(|(pair_id, (price, timestamp))| {
-            let max_age = pair_feeds.get(&pair_id).expect(ERROR_INVALID_PRICE);
-            let min_timestamp = current_time.add_seconds(-max_age).expect(ERROR_ARITHMETIC);
+        let mut quotes: HashMap<PairId, Vec<(Decimal, Decimal)>> = HashMap::new();
+        let mut timestamps: HashMap<PairId, Instant> = HashMap::new();
+        for (oracle_index, prices_with_timestamp) in oracle_prices.into_iter().enumerate() {
+            for (pair_id, (price, timestamp, confidence)) in prices_with_timestamp.into_iter() {
+                let max_age = pair_feeds.get(&pair_id).expect(ERROR_INVALID_PRICE);
+                let min_timestamp = current_time.add_seconds(-max_age).expect(ERROR_ARITHMETIC);
 
+                assert!(
+                    timestamp.compare(min_timestamp, TimeComparisonOperator::Gt),
+                    "{}, VALUE:{}, REQUIRED:{}, OP:> |", ERROR_PRICE_TOO_OLD, timestamp.seconds_since_unix_epoch, min_timestamp.seconds_since_unix_epoch
+                );
+                assert!(
+                    price.is_positive(), 
+                    "{}, VALUE:{}, REQUIRED:0, OP:>", ERROR_INVALID_PRICE, price
+                );
+                assert!(
+                    (price - confidence).is_positive(),
+                    "{}, VALUE:{}, REQUIRED:0, OP:>", ERROR_INVALID_PRICE, price - confidence
+                );
+                if oracle_index == 0 {
+                    timestamps.insert(pair_id.clone(), timestamp);
+                }
+                quotes.entry(pair_id).or_default().push((price, confidence));
+            }
+        }
+
+        let mut confidences = HashMap::new();
+        let mut prices: HashMap<PairId, Decimal> = quotes.into_iter().map(|(pair_id, mut quotes)| {
+            quotes.sort();
+            let price_min = quotes[0].0;
+            let price_max = quotes[quotes.len() - 1].0;
+            let tolerance = config.price_tolerance(&pair_id);
             assert!(
-                timestamp.compare(min_timestamp, TimeComparisonOperator::Gt),
-                "{}, VALUE:{}, REQUIRED:{}, OP:> |", ERROR_PRICE_TOO_OLD, timestamp.seconds_since_unix_epoch, min_timestamp.seconds_since_unix_epoch
-            );
-            assert!(
-                price.is_positive(), 
-                "{}, VALUE:{}, REQUIRED:0, OP:>", ERROR_INVALID_PRICE, price
+                price_max - price_min <= price_min * tolerance,
+                "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_PRICE_DISAGREEMENT, (price_max - price_min) / price_min, tolerance
             );
+
+            let mid = quotes.len() / 2;
+            let price = if quotes.len() % 2 == 0 {
+                (quotes[mid - 1].0 + quotes[mid].0) / dec!(2)
+            } else {
+                quotes[mid].0
+            };
+            let confidence = quotes.iter().map(|(_, confidence)| *confidence).max().unwrap();
+            confidences.insert(pair_id.clone(), confidence);
             (pair_id, price)
         }).collect();
+        for (pair_id, price) in settlement_prices.into_iter() {
+            confidences.insert(pair_id.clone(), dec!(0));
+            prices.insert(pair_id, price);
+        }
 
         let resource_map = resource_feeds.into_iter().map(|(resource, (pair_id, _))| (resource, pair_id)).collect();
         Self {
+            oracles,
             prices,
+            confidences,
+            timestamps,
             resource_map,
         }
     }
 
+    /// Oracle over caller provided prices, for read only previews that must not push or load prices
+    pub fn from_prices(oracle: Global<Oracle>, prices: HashMap<PairId, Decimal>) -> Self {
+        for (_, price) in prices.iter() {
+            assert!(
+                price.is_positive(), 
+                "{}, VALUE:{}, REQUIRED:0, OP:>", ERROR_INVALID_PRICE, price
+            );
+        }
+        let confidences = prices.keys().map(|pair_id| (pair_id.clone(), dec!(0))).collect();
+
+        Self {
+            oracles: vec![oracle],
+            prices,
+            confidences,
+            timestamps: HashMap::new(),
+            resource_map: HashMap::new(),
+        }
+    }
+
     pub fn price(&self, pair_id: &PairId) -> Decimal {
         *self.prices.get(pair_id).expect(ERROR_MISSING_PRICE)
     }
 
+    pub fn price_bid(&self, pair_id: &PairId) -> Decimal {
+        self.price(pair_id) - self.confidence(pair_id)
+    }
+
+    pub fn price_ask(&self, pair_id: &PairId) -> Decimal {
+        self.price(pair_id) + self.confidence(pair_id)
+    }
+
+    pub fn price_fill(&self, pair_id: &PairId, amount: Decimal) -> Decimal {
+        if amount.is_positive() {
+            self.price_ask(pair_id)
+        } else {
+            self.price_bid(pair_id)
+        }
+    }
+
+    pub fn confidence(&self, pair_id: &PairId) -> Decimal {
+        *self.confidences.get(pair_id).expect(ERROR_MISSING_PRICE)
+    }
+
+    /// Asserts that the primary oracle prices of the pairs were published after the time, pairs with a
+    /// settlement price are skipped
+    pub fn assert_published_after(&self, pair_ids: &HashSet<PairId>, time: Instant) {
+        for timestamp in pair_ids.iter().filter_map(|pair_id| self.timestamps.get(pair_id)) {
+            assert!(
+                timestamp.compare(time, TimeComparisonOperator::Gt),
+                "{}, VALUE:{}, REQUIRED:{}, OP:> |", ERROR_PRICE_BEFORE_SUBMISSION, timestamp.seconds_since_unix_epoch, time.seconds_since_unix_epoch
+            );
+        }
+    }
+
+    pub fn is_published_after(&self, pair_ids: &HashSet<PairId>, time: Instant) -> bool {
+        pair_ids.iter()
+            .filter_map(|pair_id| self.timestamps.get(pair_id))
+            .all(|timestamp| timestamp.compare(time, TimeComparisonOperator::Gt))
+    }
+
+    /// Whether the fill price of the amount, the ask when buying and the bid when selling, reached the limit
+    /// between the timestamps on every configured oracle
+    pub fn traded_through(&self, pair_id: &PairId, amount: Decimal, price_limit: PriceLimit, from: Instant, to: Instant) -> bool {
+        self.oracles.iter().all(|oracle| {
+            oracle.get_high_low_with_auth(pair_id.clone(), from, to).is_some_and(|high_low| {
+                let (high, low) = if amount.is_positive() {
+                    (high_low.ask_high, high_low.ask_low)
+                } else {
+                    (high_low.bid_high, high_low.bid_low)
+                };
+                price_limit.compare(high) || price_limit.compare(low)
+            })
+        })
+    }
+
+    pub fn resource_pair_id(&self, resource: &ResourceAddress) -> Option<&PairId> {
+        self.resource_map.get(resource)
+    }
+
     pub fn price_resource(&self, resource: ResourceAddress) -> Decimal {
         let pair_id = self.resource_map.get(&resource).expect(ERROR_MISSING_RESOURCE_FEED);
         self.price(pair_id)
     }
+
+    pub fn price_resource_bid(&self, resource: ResourceAddress) -> Decimal {
+        let pair_id = self.resource_map.get(&resource).expect(ERROR_MISSING_RESOURCE_FEED);
+        self.price_bid(pair_id)
+    }
 }
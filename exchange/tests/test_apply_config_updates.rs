@@ -0,0 +1,178 @@
+#[path = "tests_common/mod.rs"]
+mod tests_common;
+use tests_common::*;
+
+#[test]
+fn test_apply_config_updates_pair_config_delay() {
+    let mut interface = get_setup();
+    let delay = interface.get_exchange_config().config_delay_pairs;
+    assert_eq!(delay, 3600);
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    let time_1 = interface.increment_ledger_time(1);
+    let result_1 = interface.schedule_pair_configs(vec![pair_config.clone()]).expect_commit_success().clone();
+
+    let event_1: EventConfigUpdateScheduled = interface.parse_event(&result_1);
+    assert_eq!(event_1.id, 0);
+    assert_eq!(event_1.activation, time_1.add_seconds(delay).unwrap());
+    assert_eq!(interface.get_pair_configs_len(), 0);
+
+    let pending_updates_1 = interface.get_pending_config_updates();
+    assert_eq!(pending_updates_1.len(), 1);
+    assert_eq!(pending_updates_1[0].0, 0);
+    assert_eq!(pending_updates_1[0].1.activation, time_1.add_seconds(delay).unwrap());
+
+    interface.increment_ledger_time(delay - 1);
+    interface.apply_config_updates(vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_CONFIG_UPDATE_NOT_DUE));
+
+    interface.increment_ledger_time(1);
+    let result_2 = interface.apply_config_updates(vec![0]).expect_commit_success().clone();
+
+    let event_2: EventConfigUpdateActivated = interface.parse_event(&result_2);
+    assert_eq!(event_2.id, 0);
+    let event_2_configs: EventPairConfigUpdates = interface.parse_event(&result_2);
+    assert_eq!(event_2_configs.configs[0].pair_id, pair_config.pair_id);
+
+    assert_eq!(interface.get_pair_configs_len(), 1);
+    assert_eq!(interface.get_pending_config_updates().len(), 0);
+
+    interface.apply_config_updates(vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_CONFIG_UPDATE));
+}
+
+#[test]
+fn test_apply_config_updates_cancelled() {
+    let mut interface = get_setup();
+
+    let exchange_config_0 = interface.get_exchange_config();
+    let mut exchange_config_1 = exchange_config_0.clone();
+    exchange_config_1.skew_ratio_cap = dec!(0);
+    interface.schedule_exchange_config(exchange_config_1).expect_commit_success();
+    assert_eq!(interface.get_pending_config_updates().len(), 1);
+
+    let result_2 = interface.cancel_config_update(0).expect_commit_success().clone();
+    let event_2: EventConfigUpdateCancelled = interface.parse_event(&result_2);
+    assert_eq!(event_2.id, 0);
+    assert_eq!(interface.get_pending_config_updates().len(), 0);
+
+    interface.increment_ledger_time(exchange_config_0.config_delay_exchange);
+    interface.apply_config_updates(vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_CONFIG_UPDATE));
+    interface.cancel_config_update(0)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_CONFIG_UPDATE));
+
+    let exchange_config_3 = interface.get_exchange_config();
+    assert_eq!(exchange_config_3.skew_ratio_cap, exchange_config_0.skew_ratio_cap);
+}
+
+#[test]
+fn test_apply_config_updates_delay_min() {
+    let mut interface = get_setup();
+
+    let mut exchange_config_0 = interface.get_exchange_config();
+    exchange_config_0.config_delay_pairs = 0;
+    interface.schedule_exchange_config(exchange_config_0.clone())
+        .expect_specific_failure(|err| check_error_msg(err, "Invalid config delay"));
+
+    exchange_config_0.config_delay_pairs = 3599;
+    interface.schedule_exchange_config(exchange_config_0)
+        .expect_specific_failure(|err| check_error_msg(err, "Invalid config delay"));
+}
+
+#[test]
+fn test_apply_config_updates_delay_reduction() {
+    let mut interface = get_setup();
+
+    let mut exchange_config_0 = interface.get_exchange_config();
+    exchange_config_0.config_delay_collaterals = 2592000;
+    interface.update_exchange_config(exchange_config_0.clone()).expect_commit_success();
+    assert_eq!(interface.get_exchange_config().config_delay_collaterals, 2592000);
+
+    let mut exchange_config_1 = exchange_config_0.clone();
+    exchange_config_1.config_delay_collaterals = 86400;
+    let time_1 = interface.increment_ledger_time(1);
+    let result_1 = interface.schedule_exchange_config(exchange_config_1).expect_commit_success().clone();
+
+    let event_1: EventConfigUpdateScheduled = interface.parse_event(&result_1);
+    assert_eq!(event_1.activation, time_1.add_seconds(2592000).unwrap());
+
+    interface.increment_ledger_time(exchange_config_0.config_delay_exchange);
+    interface.apply_config_updates(vec![event_1.id])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_CONFIG_UPDATE_NOT_DUE));
+
+    interface.increment_ledger_time(2592000 - exchange_config_0.config_delay_exchange);
+    interface.apply_config_updates(vec![event_1.id]).expect_commit_success();
+    assert_eq!(interface.get_exchange_config().config_delay_collaterals, 86400);
+
+    let mut exchange_config_2 = interface.get_exchange_config();
+    exchange_config_2.config_delay_collaterals = 172800;
+    let time_2 = interface.ledger_time();
+    let result_2 = interface.schedule_exchange_config(exchange_config_2.clone()).expect_commit_success().clone();
+
+    let event_2: EventConfigUpdateScheduled = interface.parse_event(&result_2);
+    assert_eq!(event_2.activation, time_2.add_seconds(exchange_config_2.config_delay_exchange).unwrap());
+}
+
+#[test]
+fn test_apply_config_updates_pair_status_restriction() {
+    let mut interface = get_setup();
+    let delay = interface.get_exchange_config().config_delay_pairs;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let pair_config_1 = PairConfig {
+        status: PairStatus::ReduceOnly,
+        ..pair_config.clone()
+    };
+    let result_1 = interface.schedule_pair_configs(vec![pair_config_1]).expect_commit_success().clone();
+    let event_1: EventConfigUpdateActivated = interface.parse_event(&result_1);
+    assert_eq!(interface.get_pending_config_updates().len(), 0);
+    assert_eq!(interface.get_pair_configs(1, None)[0].status, PairStatus::ReduceOnly);
+
+    let pair_config_2 = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config.clone()
+    };
+    let result_2 = interface.schedule_pair_configs(vec![pair_config_2.clone()]).expect_commit_success().clone();
+    let event_2: EventConfigUpdateActivated = interface.parse_event(&result_2);
+    assert_eq!(event_2.id, event_1.id + 1);
+    assert_eq!(interface.get_pair_configs(1, None)[0].status, PairStatus::Halted);
+
+    let pair_config_3 = PairConfig {
+        status: PairStatus::Halted,
+        fee_0: dec!(0.001),
+        ..pair_config.clone()
+    };
+    let pair_config_4 = PairConfig {
+        status: PairStatus::Settled(dec!(60000)),
+        ..pair_config.clone()
+    };
+    for pair_config in [pair_config.clone(), pair_config_3, pair_config_4] {
+        interface.schedule_pair_configs(vec![pair_config]).expect_commit_success();
+        assert_eq!(interface.get_pending_config_updates().len(), 1);
+        assert_eq!(interface.get_pair_configs(1, None)[0].status, PairStatus::Halted);
+
+        let (id, pending_update) = interface.get_pending_config_updates()[0].clone();
+        assert_eq!(pending_update.activation, interface.ledger_time().add_seconds(delay).unwrap());
+        interface.cancel_config_update(id).expect_commit_success();
+    }
+}
+
+#[test]
+fn test_apply_config_updates_pending_max() {
+    let mut interface = get_setup();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    for _ in 0..32 {
+        interface.schedule_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+    }
+    assert_eq!(interface.get_pending_config_updates().len(), 32);
+
+    interface.schedule_pair_configs(vec![pair_config.clone()])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_PENDING_CONFIG_UPDATES_FULL));
+
+    interface.cancel_config_update(0).expect_commit_success();
+    interface.schedule_pair_configs(vec![pair_config]).expect_commit_success();
+}
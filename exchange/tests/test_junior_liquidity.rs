@@ -0,0 +1,211 @@
+#[path = "tests_common/mod.rs"]
+mod tests_common;
+use tests_common::*;
+
+#[test]
+fn test_add_junior_liquidity_normal() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let junior_lp_resource = interface.resources.junior_lp_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.fee_share_senior = dec!(0);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+    let pool_value_0 = interface.get_pool_value();
+
+    let base_input_1 = dec!(10000);
+    let junior_lp_balance_1 = interface.test_account_balance(junior_lp_resource);
+    let result = interface.add_junior_liquidity((base_resource, base_input_1)).expect_commit_success().clone();
+
+    let junior_lp_output_1 = interface.test_account_balance(junior_lp_resource) - junior_lp_balance_1;
+    let pool_value_1 = interface.get_pool_value();
+
+    let fee = base_input_1 * exchange_config.fee_liquidity_add;
+    let fee_protocol = fee * exchange_config.fee_share_protocol;
+    let fee_treasury = fee * exchange_config.fee_share_treasury;
+    let fee_pool = fee - fee_protocol - fee_treasury;
+
+    assert_eq!(junior_lp_output_1, base_input_1 - fee);
+    assert_eq!(pool_value_1 - pool_value_0, base_input_1 - fee + fee_pool);
+
+    let pool_details = interface.get_pool_details();
+    assert_eq!(pool_details.senior_value, pool_value_0);
+    assert_eq!(pool_details.junior_value, pool_value_1 - pool_value_0);
+    assert_eq!(pool_details.junior_lp_supply, junior_lp_output_1);
+
+    let event: EventJuniorLiquidityChange = interface.parse_event(&result);
+    assert_eq!(event.lp_price, dec!(1));
+    assert_eq!(event.lp_amount, junior_lp_output_1);
+    assert_eq!(event.amount, base_input_1);
+    assert_eq!(event.fee_pool, -fee_pool);
+    assert_eq!(event.fee_protocol, -fee_protocol);
+    assert_eq!(event.fee_treasury, -fee_treasury);
+}
+
+#[test]
+fn test_remove_junior_liquidity_normal() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let junior_lp_resource = interface.resources.junior_lp_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.fee_share_senior = dec!(0);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.add_junior_liquidity((base_resource, dec!(10000))).expect_commit_success();
+
+    let pool_details_1 = interface.get_pool_details();
+    let base_balance_1 = interface.test_account_balance(base_resource);
+    let lp_input_1 = pool_details_1.junior_lp_supply / dec!(2);
+    let result = interface.remove_junior_liquidity((junior_lp_resource, lp_input_1)).expect_commit_success().clone();
+
+    let base_output_1 = interface.test_account_balance(base_resource) - base_balance_1;
+    let pool_details_2 = interface.get_pool_details();
+
+    let lp_price = pool_details_1.junior_value / pool_details_1.junior_lp_supply;
+    let value = lp_input_1 * lp_price;
+    let fee = value * exchange_config.fee_liquidity_remove;
+    let fee_protocol = fee * exchange_config.fee_share_protocol;
+    let fee_treasury = fee * exchange_config.fee_share_treasury;
+    let fee_pool = fee - fee_protocol - fee_treasury;
+
+    assert_eq!(base_output_1, value - fee);
+    assert_eq!(pool_details_2.senior_value, pool_details_1.senior_value);
+    assert_eq!(pool_details_2.junior_value, pool_details_1.junior_value - value + fee_pool);
+
+    let event: EventJuniorLiquidityChange = interface.parse_event(&result);
+    assert_eq!(event.lp_price, lp_price);
+    assert_eq!(event.lp_amount, -lp_input_1);
+    assert_eq!(event.amount, -base_output_1);
+}
+
+#[test]
+fn test_remove_junior_liquidity_invalid_token() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.add_junior_liquidity((base_resource, dec!(10000))).expect_commit_success();
+
+    interface.remove_junior_liquidity((lp_resource, dec!(1000)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_JUNIOR_LP_TOKEN));
+}
+
+#[test]
+fn test_junior_liquidity_fee_share_senior() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.fee_share_senior = dec!(1);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.add_junior_liquidity((base_resource, dec!(100000))).expect_commit_success();
+
+    let pool_details_1 = interface.get_pool_details();
+    interface.add_junior_liquidity((base_resource, dec!(10000))).expect_commit_success();
+    let pool_details_2 = interface.get_pool_details();
+
+    let pool_value_1 = pool_details_1.senior_value + pool_details_1.junior_value;
+    let pool_value_2 = pool_details_2.senior_value + pool_details_2.junior_value;
+    assert!(pool_details_2.senior_value > pool_details_1.senior_value);
+    assert!(pool_details_2.senior_value - pool_details_1.senior_value < pool_value_2 - pool_value_1);
+}
+
+#[test]
+fn test_junior_liquidity_absorbs_losses() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.add_junior_liquidity((base_resource, dec!(1000))).expect_commit_success();
+
+    let result_0 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, dec!(10000))],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_0.new_component_addresses()[0];
+
+    let trade_size_1 = dec!(0.1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_1,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time_2 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60000),
+                timestamp: time_2,
+            },
+        ])
+    ).expect_commit_success();
+
+    let pool_details_2 = interface.get_pool_details();
+    assert!(pool_details_2.junior_value.is_positive());
+
+    let time_3 = interface.increment_ledger_time(1);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(75000),
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let pool_value_3 = interface.get_pool_value();
+    let pool_details_3 = interface.get_pool_details();
+    assert_eq!(pool_details_3.junior_value, dec!(0));
+    assert_eq!(pool_details_3.senior_value, pool_value_3);
+    assert!(pool_details_3.senior_value < pool_details_2.senior_value);
+}
+
+#[test]
+fn test_remove_junior_liquidity_all() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let junior_lp_resource = interface.resources.junior_lp_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.fee_share_senior = dec!(0);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.add_junior_liquidity((base_resource, dec!(10000))).expect_commit_success();
+
+    let pool_details_1 = interface.get_pool_details();
+    interface.remove_junior_liquidity((junior_lp_resource, pool_details_1.junior_lp_supply)).expect_commit_success();
+
+    let pool_value_2 = interface.get_pool_value();
+    let pool_details_2 = interface.get_pool_details();
+    assert_eq!(pool_details_2.junior_lp_supply, dec!(0));
+    assert_eq!(pool_details_2.junior_value, dec!(0));
+    assert_eq!(pool_details_2.senior_value, pool_value_2);
+    assert_eq!(pool_details_2.senior_book, pool_value_2);
+    assert!(pool_details_2.senior_book > pool_details_1.senior_book);
+}
@@ -541,6 +541,17 @@ fn test_liquidate_many_positions_collaterals_and_orders() {
             funding_share: dec!(0.02),
             fee_0: dec!(0.0005),
             fee_1: dec!(0.0000000005),
+            oi_side_ratio_max: dec!(0),
+            oi_combined_ratio_max: dec!(0),
+            oi_value_floor: dec!(0),
+            oi_value_ceiling: dec!(0),
+            margin_tiers: vec![],
+            fee_model: FeeModel::LinearSkew,
+            skew_rebate_rate: dec!(0),
+            funding_model: FundingModel::Skew,
+            price_tolerance: dec!(0.005),
+            mark_ema_seconds: 0,
+            status: PairStatus::Active,
         };
         pair_ids.insert(pair_id.clone());
         position_pair_ids.push(pair_id.clone());
@@ -898,6 +909,300 @@ fn test_liquidate_sufficient_margin() {
     ).expect_specific_failure(|err| check_error_msg(err, ERROR_LIQUIDATION_SUFFICIENT_MARGIN));
 }
 
+#[test]
+fn test_liquidate_mark_price() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let btc_resource = interface.mint_test_token(dec!(100), 8);
+    
+    let collateral_config = CollateralConfig {
+        pair_id: "BTC/USD".to_string(),
+        price_age_max: 5,
+        discount: dec!(0.90),
+        margin: dec!(0.01),
+    };
+    interface.update_collateral_configs(vec![
+        (btc_resource, collateral_config.clone()),
+    ]).expect_commit_success();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.mark_ema_seconds = 600;
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(75);
+    let btc_input_1 = dec!(0.0006);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![
+            (base_resource, base_input_1),
+            (btc_resource, btc_input_1),
+        ], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.02);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.mark_price, price_3);
+
+    let base_input_4 = dec!(100);
+    let price_4 = dec!(55000);
+    let time_4 = interface.increment_ledger_time(30);
+    interface.liquidate(
+        margin_account_component, 
+        (base_resource, base_input_4), 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_4,
+                timestamp: time_4,
+            },
+        ]),
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_LIQUIDATION_SUFFICIENT_MARGIN));
+
+    let time_5 = interface.increment_ledger_time(3000);
+    let result_5 = interface.liquidate(
+        margin_account_component, 
+        (base_resource, base_input_4), 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_4,
+                timestamp: time_5,
+            },
+        ]),
+    ).expect_commit_success().clone();
+
+    let event: EventLiquidate = interface.parse_event(&result_5);
+    assert_eq!(event.position_prices, vec![(pair_config.pair_id.clone(), price_4)]);
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert!(pair_details.pool_position.mark_price > price_4);
+    assert!(pair_details.pool_position.mark_price < price_4 * dec!(1.001));
+}
+
+#[test]
+fn test_liquidate_halted_pair() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let btc_resource = interface.mint_test_token(dec!(100), 8);
+    
+    let collateral_config = CollateralConfig {
+        pair_id: "BTC/USD".to_string(),
+        price_age_max: 5,
+        discount: dec!(0.90),
+        margin: dec!(0.01),
+    };
+    interface.update_collateral_configs(vec![
+        (btc_resource, collateral_config.clone()),
+    ]).expect_commit_success();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.mark_ema_seconds = 600;
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(75);
+    let btc_input_1 = dec!(0.0006);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![
+            (base_resource, base_input_1),
+            (btc_resource, btc_input_1),
+        ], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.02);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let price_4 = dec!(55000);
+    let time_4 = interface.increment_ledger_time(3000);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_4,
+                timestamp: time_4,
+            },
+        ])
+    ).expect_commit_success();
+
+    let pair_config_5 = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_5]).expect_commit_success();
+    let mark_price_5 = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].pool_position.mark_price;
+
+    let base_input_6 = dec!(100);
+    let time_6 = interface.increment_ledger_time(1);
+    let result_6 = interface.liquidate(
+        margin_account_component, 
+        (base_resource, base_input_6), 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_4,
+                timestamp: time_6,
+            },
+        ]),
+    ).expect_commit_success().clone();
+
+    let event: EventLiquidate = interface.parse_event(&result_6);
+    assert_eq!(event.position_prices, vec![(pair_config.pair_id.clone(), mark_price_5)]);
+
+    let account_details = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details.positions.len(), 0);
+}
+
+#[test]
+fn test_liquidate_halted_pair_stale_price() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(100);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let price_4 = dec!(59000);
+    let time_4 = interface.increment_ledger_time(1);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_4,
+                timestamp: time_4,
+            },
+        ])
+    ).expect_commit_success();
+
+    let pair_config_5 = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_5]).expect_commit_success();
+    let pool_position_5 = interface.get_pool_position(pair_config.pair_id.clone());
+    assert_eq!(pool_position_5.mark_price, price_4);
+
+    interface.increment_ledger_time(3600);
+    interface.update_pairs(vec![pair_config.pair_id.clone()], None).expect_commit_success();
+    let pool_position_6 = interface.get_pool_position(pair_config.pair_id.clone());
+    assert_eq!(pool_position_6.last_update, pool_position_5.last_update);
+    assert_eq!(pool_position_6.funding_long_index, pool_position_5.funding_long_index);
+
+    let base_input_7 = dec!(100);
+    let result_7 = interface.liquidate(
+        margin_account_component, 
+        (base_resource, base_input_7), 
+        None,
+    ).expect_commit_success().clone();
+
+    let event: EventLiquidate = interface.parse_event(&result_7);
+    assert_eq!(event.position_prices, vec![(pair_config.pair_id.clone(), price_4)]);
+
+    let account_details = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details.positions.len(), 0);
+}
+
 #[test]
 fn test_liquidate_insufficient_payment() {
     let mut interface = get_setup();
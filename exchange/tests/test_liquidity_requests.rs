@@ -0,0 +1,392 @@
+#[path = "tests_common/mod.rs"]
+mod tests_common;
+use tests_common::*;
+
+#[test]
+fn test_liquidity_requests_remove_normal() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let exchange_config = interface.get_exchange_config();
+    let receipt_resource = interface.get_pool_details().withdrawal_receipt_resource;
+
+    let base_input_0 = dec!(100000);
+    let lp_balance_0 = interface.test_account_balance(lp_resource);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_balance_1 = interface.test_account_balance(lp_resource);
+    let lp_output_1 = lp_balance_1 - lp_balance_0;
+    let lp_input_1 = lp_output_1 / dec!(2);
+    let result = interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success().clone();
+
+    let event: EventLiquidityWithdrawal = interface.parse_event(&result);
+    assert_eq!(event.index, 0);
+    assert_eq!(event.status, LiquidityRequestStatus::Queued);
+    assert_eq!(event.lp_amount, lp_input_1);
+    assert_eq!(interface.test_account_balance(receipt_resource), dec!(1));
+    assert_eq!(interface.get_pool_details().withdrawals_len, 1);
+
+    let pool_value_2 = interface.get_pool_value();
+    let result = interface.process_liquidity_requests(10, vec![]).expect_commit_success().clone();
+
+    let pool_value_3 = interface.get_pool_value();
+    let lp_price = pool_value_2 / lp_output_1;
+    let value = lp_input_1 * lp_price;
+    let fee = value * exchange_config.fee_liquidity_remove;
+    let fee_protocol = fee * exchange_config.fee_share_protocol;
+    let fee_treasury = fee * exchange_config.fee_share_treasury;
+    let fee_pool = fee - fee_protocol - fee_treasury;
+
+    assert_eq!(pool_value_3 - pool_value_2, -value + fee_pool);
+    assert_eq!(interface.get_pool_details().withdrawals_start, 1);
+
+    let event: EventLiquidityChange = interface.parse_event(&result);
+    assert_eq!(event.lp_price, lp_price);
+    assert_eq!(event.lp_amount, -lp_input_1);
+    assert_eq!(event.amount, -(value - fee));
+
+    let base_balance_3 = interface.test_account_balance(base_resource);
+    let result = interface.claim_remove_liquidity((receipt_resource, NonFungibleLocalId::integer(0))).expect_commit_success().clone();
+    let base_balance_4 = interface.test_account_balance(base_resource);
+
+    assert_eq!(base_balance_4 - base_balance_3, value - fee);
+    assert_eq!(interface.test_account_balance(receipt_resource), dec!(0));
+
+    let event: EventLiquidityWithdrawal = interface.parse_event(&result);
+    assert_eq!(event.status, LiquidityRequestStatus::Claimed);
+    assert_eq!(event.amount, value - fee);
+}
+
+#[test]
+fn test_liquidity_requests_remove_cancel() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let receipt_resource = interface.get_pool_details().withdrawal_receipt_resource;
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_balance_1 = interface.test_account_balance(lp_resource);
+    let lp_input_1 = lp_balance_1 / dec!(4);
+    interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success();
+    assert_eq!(interface.test_account_balance(lp_resource), lp_balance_1 - lp_input_1);
+
+    let result = interface.cancel_remove_liquidity((receipt_resource, NonFungibleLocalId::integer(0))).expect_commit_success().clone();
+    assert_eq!(interface.test_account_balance(lp_resource), lp_balance_1);
+    assert_eq!(interface.test_account_balance(receipt_resource), dec!(0));
+
+    let event: EventLiquidityWithdrawal = interface.parse_event(&result);
+    assert_eq!(event.status, LiquidityRequestStatus::Cancelled);
+    assert_eq!(event.lp_amount, lp_input_1);
+
+    let pool_value_2 = interface.get_pool_value();
+    interface.process_liquidity_requests(10, vec![]).expect_commit_success();
+    assert_eq!(interface.get_pool_value(), pool_value_2);
+}
+
+#[test]
+fn test_liquidity_requests_remove_claim_before_fill() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let receipt_resource = interface.get_pool_details().withdrawal_receipt_resource;
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_input_1 = interface.test_account_balance(lp_resource) / dec!(2);
+    interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success();
+
+    interface.claim_remove_liquidity((receipt_resource, NonFungibleLocalId::integer(0)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_WITHDRAWAL_NOT_FILLED));
+}
+
+#[test]
+fn test_liquidity_requests_remove_cancel_after_fill() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let receipt_resource = interface.get_pool_details().withdrawal_receipt_resource;
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_input_1 = interface.test_account_balance(lp_resource) / dec!(2);
+    interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success();
+    interface.process_liquidity_requests(10, vec![]).expect_commit_success();
+
+    interface.cancel_remove_liquidity((receipt_resource, NonFungibleLocalId::integer(0)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_WITHDRAWAL_NOT_QUEUED));
+}
+
+#[test]
+fn test_liquidity_requests_remove_invalid_token() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let fake_lp_resource = interface.mint_test_token(dec!(1000000), 18);
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    interface.queue_remove_liquidity((fake_lp_resource, dec!(10000)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_LP_TOKEN));
+}
+
+#[test]
+fn test_liquidity_requests_remove_below_min() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let exchange_config = interface.get_exchange_config();
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_input_1 = exchange_config.lp_withdrawal_min / dec!(2);
+    interface.queue_remove_liquidity((lp_resource, lp_input_1))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_WITHDRAWAL_AMOUNT_MIN_NOT_MET));
+}
+
+#[test]
+fn test_liquidity_requests_keeper_reward_per_batch() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let keeper_reward_resource = interface.resources.keeper_reward_resource;
+    let exchange_config = interface.get_exchange_config();
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_input_1 = interface.test_account_balance(lp_resource) / dec!(4);
+    interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success();
+    interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success();
+    interface.queue_remove_liquidity((lp_resource, lp_input_1)).expect_commit_success();
+
+    let reward_balance_2 = interface.test_account_balance(keeper_reward_resource);
+    interface.process_liquidity_requests(10, vec![]).expect_commit_success();
+    assert_eq!(interface.get_pool_details().withdrawals_start, 3);
+    assert_eq!(interface.test_account_balance(keeper_reward_resource) - reward_balance_2, exchange_config.reward_keeper);
+
+    interface.process_liquidity_requests(10, vec![]).expect_commit_success();
+    assert_eq!(interface.test_account_balance(keeper_reward_resource) - reward_balance_2, exchange_config.reward_keeper);
+}
+
+#[test]
+fn test_liquidity_requests_add_normal() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let exchange_config = interface.get_exchange_config();
+    let receipt_resource = interface.get_pool_details().deposit_receipt_resource;
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let lp_balance_1 = interface.test_account_balance(lp_resource);
+    let pool_value_1 = interface.get_pool_value();
+    let lp_supply_1 = interface.get_pool_details().lp_supply;
+    let result = interface.queue_add_liquidity((base_resource, base_input_1)).expect_commit_success().clone();
+
+    let event: EventLiquidityDeposit = interface.parse_event(&result);
+    assert_eq!(event.index, 0);
+    assert_eq!(event.status, LiquidityRequestStatus::Queued);
+    assert_eq!(event.amount, base_input_1);
+    assert_eq!(interface.get_pool_value(), pool_value_1);
+    assert_eq!(interface.get_pool_details().deposits_len, 1);
+
+    let result = interface.process_liquidity_requests(10, vec![]).expect_commit_success().clone();
+
+    let lp_price = pool_value_1 / lp_supply_1;
+    let fee = base_input_1 * exchange_config.fee_liquidity_add;
+    let lp_amount = (base_input_1 - fee) / lp_price;
+
+    let event: EventLiquidityChange = interface.parse_event(&result);
+    assert_eq!(event.lp_price, lp_price);
+    assert_eq!(event.lp_amount, lp_amount);
+    assert_eq!(event.amount, base_input_1);
+    assert_eq!(interface.get_pool_details().deposits_start, 1);
+
+    interface.claim_add_liquidity((receipt_resource, NonFungibleLocalId::integer(0))).expect_commit_success();
+    assert_eq!(interface.test_account_balance(lp_resource) - lp_balance_1, lp_amount);
+    assert_eq!(interface.test_account_balance(receipt_resource), dec!(0));
+}
+
+#[test]
+fn test_liquidity_requests_add_cancel() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let receipt_resource = interface.get_pool_details().deposit_receipt_resource;
+
+    let base_balance_0 = interface.test_account_balance(base_resource);
+    let base_input_0 = dec!(10000);
+    interface.queue_add_liquidity((base_resource, base_input_0)).expect_commit_success();
+    assert_eq!(interface.test_account_balance(base_resource), base_balance_0 - base_input_0);
+
+    let result = interface.cancel_add_liquidity((receipt_resource, NonFungibleLocalId::integer(0))).expect_commit_success().clone();
+    assert_eq!(interface.test_account_balance(base_resource), base_balance_0);
+
+    let event: EventLiquidityDeposit = interface.parse_event(&result);
+    assert_eq!(event.status, LiquidityRequestStatus::Cancelled);
+    assert_eq!(event.amount, base_input_0);
+
+    interface.claim_add_liquidity((receipt_resource, NonFungibleLocalId::integer(0)))
+        .expect_commit_failure();
+}
+
+#[test]
+fn test_liquidity_requests_add_invalid_token() {
+    let mut interface = get_setup();
+    let fake_base_resource = interface.mint_test_token(dec!(1000000), 18);
+
+    interface.queue_add_liquidity((fake_base_resource, dec!(10000)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_PAYMENT_TOKEN));
+}
+
+#[test]
+fn test_liquidity_requests_stale_price() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("TEST/USD".into());
+    let idle_pair_config = default_pair_config("IDLE/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone(), idle_pair_config]).expect_commit_success();
+    interface.add_liquidity((base_resource, dec!(1000000))).expect_commit_success();
+
+    let result_1 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, dec!(10000))],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        dec!(1),
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    let time_2 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(1),
+                timestamp: time_2,
+            },
+        ])
+    ).expect_commit_success();
+
+    interface.queue_add_liquidity((base_resource, dec!(10000))).expect_commit_success();
+
+    interface.increment_ledger_time(pair_config.price_age_max + 1);
+    interface.process_liquidity_requests(10, vec![])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_PRICE_TOO_OLD));
+
+    let time_4 = interface.ledger_time();
+    interface.process_liquidity_requests(
+        10, 
+        vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(1),
+                timestamp: time_4,
+            },
+        ]
+    ).expect_commit_success();
+    assert_eq!(interface.get_pool_details().deposits_start, 1);
+}
+
+#[test]
+fn test_liquidity_requests_price_before_submission() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("TEST/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+    interface.add_liquidity((base_resource, dec!(1000000))).expect_commit_success();
+
+    let result_1 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, dec!(10000))],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        dec!(1),
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    let time_2 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(1),
+                timestamp: time_2,
+            },
+        ])
+    ).expect_commit_success();
+
+    interface.queue_add_liquidity((base_resource, dec!(10000))).expect_commit_success();
+    interface.process_liquidity_requests(10, vec![]).expect_commit_success();
+    assert_eq!(interface.get_pool_details().deposits_start, 0);
+
+    let time_3 = interface.increment_ledger_time(1);
+    interface.queue_add_liquidity((base_resource, dec!(10000))).expect_commit_success();
+    interface.process_liquidity_requests(
+        10, 
+        vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(1),
+                timestamp: time_3,
+            },
+        ]
+    ).expect_commit_success();
+    assert_eq!(interface.get_pool_details().deposits_start, 1);
+
+    let time_4 = interface.increment_ledger_time(1);
+    interface.process_liquidity_requests(
+        10, 
+        vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(1),
+                timestamp: time_4,
+            },
+        ]
+    ).expect_commit_success();
+    assert_eq!(interface.get_pool_details().deposits_start, 2);
+}
+
+#[test]
+fn test_liquidity_requests_add_below_min() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let exchange_config = interface.get_exchange_config();
+
+    let base_input_0 = exchange_config.lp_deposit_min / dec!(2);
+    interface.queue_add_liquidity((base_resource, base_input_0))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_DEPOSIT_AMOUNT_MIN_NOT_MET));
+}
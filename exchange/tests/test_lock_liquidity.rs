@@ -0,0 +1,228 @@
+#[path = "tests_common/mod.rs"]
+mod tests_common;
+use tests_common::*;
+
+const LOCK_TERM: i64 = 2592000;
+
+#[test]
+fn test_lock_liquidity_claim_rewards() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let lock_resource = interface.get_pool_details().lock_receipt_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.fee_share_lock = dec!(0.5);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    let base_input_0 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let lp_input_1 = dec!(10000);
+    let result = interface.lock_liquidity((lp_resource, lp_input_1), LOCK_TERM).expect_commit_success().clone();
+
+    let event: EventLiquidityLock = interface.parse_event(&result);
+    assert_eq!(event.index, 0);
+    assert_eq!(event.lp_amount, lp_input_1);
+    assert_eq!(event.term, LOCK_TERM);
+    assert_eq!(interface.get_pool_details().lock_weight, lp_input_1 * event.boost);
+    assert_eq!(interface.test_account_balance(lock_resource), dec!(1));
+
+    let base_input_2 = dec!(10000);
+    let pool_value_2 = interface.get_pool_value();
+    interface.add_liquidity((base_resource, base_input_2)).expect_commit_success();
+    let pool_value_3 = interface.get_pool_value();
+
+    let fee = base_input_2 * exchange_config.fee_liquidity_add;
+    let fee_protocol = fee * exchange_config.fee_share_protocol;
+    let fee_treasury = fee * exchange_config.fee_share_treasury;
+    let fee_lock = (fee - fee_protocol - fee_treasury) * exchange_config.fee_share_lock;
+    let fee_pool = fee - fee_protocol - fee_treasury - fee_lock;
+    assert_eq!(pool_value_3 - pool_value_2, base_input_2 - fee + fee_pool);
+
+    let base_balance_3 = interface.test_account_balance(base_resource);
+    let result = interface.claim_liquidity_lock_rewards((lock_resource, NonFungibleLocalId::integer(0))).expect_commit_success().clone();
+    let base_balance_4 = interface.test_account_balance(base_resource);
+
+    assert_eq!(base_balance_4 - base_balance_3, fee_lock);
+    assert_eq!(interface.get_pool_value(), pool_value_3);
+
+    let event: EventLiquidityLockClaim = interface.parse_event(&result);
+    assert_eq!(event.amount, fee_lock);
+
+    interface.claim_liquidity_lock_rewards((lock_resource, NonFungibleLocalId::integer(0))).expect_commit_success();
+    assert_eq!(interface.test_account_balance(base_resource), base_balance_4);
+}
+
+#[test]
+fn test_lock_liquidity_invalid_term() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+
+    interface.lock_liquidity((lp_resource, dec!(10000)), LOCK_TERM + 1)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_LOCK_TERM));
+}
+
+#[test]
+fn test_lock_liquidity_unlock() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let lock_resource = interface.get_pool_details().lock_receipt_resource;
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+
+    let lp_balance_0 = interface.test_account_balance(lp_resource);
+    let lp_input_0 = dec!(10000);
+    interface.lock_liquidity((lp_resource, lp_input_0), LOCK_TERM).expect_commit_success();
+    assert_eq!(interface.test_account_balance(lp_resource), lp_balance_0 - lp_input_0);
+
+    interface.increment_ledger_time(LOCK_TERM - 1);
+    interface.unlock_liquidity((lock_resource, NonFungibleLocalId::integer(0)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_LOCK_NOT_EXPIRED));
+
+    interface.increment_ledger_time(1);
+    interface.unlock_liquidity((lock_resource, NonFungibleLocalId::integer(0))).expect_commit_success();
+    assert_eq!(interface.test_account_balance(lp_resource), lp_balance_0);
+    assert_eq!(interface.test_account_balance(lock_resource), dec!(0));
+    assert_eq!(interface.get_pool_details().lock_weight, dec!(0));
+}
+
+#[test]
+fn test_lock_liquidity_withdrawal_priority() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let lock_resource = interface.get_pool_details().lock_receipt_resource;
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.lock_liquidity((lp_resource, dec!(10000)), LOCK_TERM).expect_commit_success();
+    interface.increment_ledger_time(LOCK_TERM);
+
+    interface.queue_remove_liquidity((lp_resource, dec!(10000))).expect_commit_success();
+    interface.queue_remove_locked_liquidity((lock_resource, NonFungibleLocalId::integer(0))).expect_commit_success();
+
+    let result = interface.process_liquidity_requests(10, vec![]).expect_commit_success().clone();
+    let event: EventLiquidityWithdrawal = interface.parse_event(&result);
+    assert_eq!(event.index, 1);
+    assert_eq!(event.status, LiquidityRequestStatus::Filled);
+    assert_eq!(interface.get_pool_details().withdrawals_start, 2);
+}
+
+#[test]
+fn test_lock_liquidity_unlock_rewards_capped() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let lp_resource = interface.resources.lp_resource;
+    let lock_resource = interface.get_pool_details().lock_receipt_resource;
+    let btc_resource = interface.mint_test_token(dec!(100), 8);
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.fee_share_lock = dec!(0.5);
+    exchange_config.fee_liquidity_remove = dec!(0);
+    exchange_config.skew_ratio_cap = dec!(1);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    let collateral_config = CollateralConfig {
+        pair_id: "BTC/USD".to_string(),
+        price_age_max: 5,
+        discount: dec!(0.90),
+        margin: dec!(0.01),
+    };
+    interface.update_collateral_configs(vec![
+        (btc_resource, collateral_config.clone()),
+    ]).expect_commit_success();
+
+    let pair_config = pair_config_zero_fees_and_funding("TEST/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    interface.lock_liquidity((lp_resource, dec!(10000)), LOCK_TERM).expect_commit_success();
+
+    let base_input_1 = dec!(100000);
+    interface.add_liquidity((base_resource, base_input_1)).expect_commit_success();
+
+    let fee = base_input_1 * exchange_config.fee_liquidity_add;
+    let fee_protocol = fee * exchange_config.fee_share_protocol;
+    let fee_treasury = fee * exchange_config.fee_share_treasury;
+    let fee_lock = (fee - fee_protocol - fee_treasury) * exchange_config.fee_share_lock;
+
+    // A loss backed by non-base collateral leaves the pool value above its base tokens
+    let result_2 = interface.create_account(
+        rule!(allow_all), 
+        vec![(btc_resource, dec!(1))], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_2.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1);
+    for trade_size in [trade_size_2, -trade_size_2] {
+        interface.margin_order_tp_sl_request(
+            0,
+            10000000000,
+            margin_account_component,
+            pair_config.pair_id.clone(),
+            trade_size,
+            false,
+            PriceLimit::None,
+            SlippageLimit::None,
+            None,
+            None,
+        ).expect_commit_success();
+    }
+
+    let btc_price = dec!(100000);
+    for (index, price) in [(0, dec!(50000)), (1, dec!(10000))] {
+        let time = interface.increment_ledger_time(1);
+        interface.process_request(
+            margin_account_component,
+            index, 
+            Some(vec![
+                Price {
+                    pair: pair_config.pair_id.clone(),
+                    quote: price,
+                    timestamp: time,
+                },
+                Price {
+                    pair: collateral_config.pair_id.clone(),
+                    quote: btc_price,
+                    timestamp: time,
+                },
+            ])
+        ).expect_commit_success();
+    }
+
+    let pool_details_3 = interface.get_pool_details();
+    let lp_input_3 = ((pool_details_3.base_tokens_amount - dec!(1)) / pool_details_3.lp_price)
+        .checked_round(18, RoundingMode::ToZero).unwrap();
+    interface.remove_liquidity((lp_resource, lp_input_3)).expect_commit_success();
+
+    let base_tokens_4 = interface.get_pool_details().base_tokens_amount;
+    assert!(base_tokens_4 < fee_lock);
+
+    interface.increment_ledger_time(LOCK_TERM);
+    let base_balance_4 = interface.test_account_balance(base_resource);
+    interface.unlock_liquidity((lock_resource, NonFungibleLocalId::integer(0))).expect_commit_success();
+    let base_balance_5 = interface.test_account_balance(base_resource);
+
+    assert_eq!(base_balance_5 - base_balance_4, base_tokens_4);
+    assert_eq!(interface.get_pool_details().base_tokens_amount, dec!(0));
+    assert_eq!(interface.get_pool_details().lock_weight, dec!(0));
+    assert_eq!(interface.test_account_balance(lock_resource), dec!(1));
+
+    interface.queue_remove_locked_liquidity((lock_resource, NonFungibleLocalId::integer(0)))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_LOCK_NO_LIQUIDITY));
+
+    interface.add_liquidity((base_resource, dec!(100000))).expect_commit_success();
+    let lp_balance_6 = interface.test_account_balance(lp_resource);
+    let base_balance_6 = interface.test_account_balance(base_resource);
+    interface.unlock_liquidity((lock_resource, NonFungibleLocalId::integer(0))).expect_commit_success();
+    let base_balance_7 = interface.test_account_balance(base_resource);
+
+    assert_eq!(base_balance_7 - base_balance_6, fee_lock - base_tokens_4);
+    assert_eq!(interface.test_account_balance(lp_resource), lp_balance_6);
+    assert_eq!(interface.test_account_balance(lock_resource), dec!(0));
+}
@@ -1918,6 +1918,17 @@ fn test_margin_order_long_exceed_oi_max() {
         funding_share: dec!(0.02),
         fee_0: dec!(0.0005),
         fee_1: dec!(0.0000000005),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     };
     interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
 
@@ -1997,6 +2008,17 @@ fn test_margin_order_short_exceed_oi_max() {
         funding_share: dec!(0.02),
         fee_0: dec!(0.0005),
         fee_1: dec!(0.0000000005),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     };
     interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
 
@@ -2052,6 +2074,204 @@ fn test_margin_order_short_exceed_oi_max() {
     ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_OI_TOO_HIGH));
 }
 
+#[test]
+fn test_margin_order_exceed_oi_side_ratio_max() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.oi_side_ratio_max = dec!(0.05);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1.001);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_OI_TOO_HIGH));
+}
+
+#[test]
+fn test_margin_order_exceed_oi_combined_ratio_max() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.oi_combined_ratio_max = dec!(0.05);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1.001);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_OI_TOO_HIGH));
+}
+
+#[test]
+fn test_margin_order_oi_ratio_max_floor() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.oi_side_ratio_max = dec!(0.05);
+    pair_config.oi_value_floor = dec!(100000);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1.001);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+}
+
+#[test]
+fn test_margin_order_exceed_oi_ratio_max_ceiling() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.oi_side_ratio_max = dec!(1);
+    pair_config.oi_value_ceiling = dec!(50000);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1.001);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_OI_TOO_HIGH));
+}
+
 #[test]
 fn test_margin_order_exceed_skew_cap() {
     let mut interface = get_setup();
@@ -2077,6 +2297,17 @@ fn test_margin_order_exceed_skew_cap() {
         funding_share: dec!(0.02),
         fee_0: dec!(0.0005),
         fee_1: dec!(0.0000000005),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     };
     interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
 
@@ -2265,6 +2496,823 @@ fn test_margin_order_insufficient_margin() {
     ).expect_specific_failure(|err| check_error_msg(err, ERROR_INSUFFICIENT_MARGIN));
 }
 
+#[test]
+fn test_margin_order_insufficient_margin_tier() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.margin_tiers = vec![(dec!(50000), dec!(0.02), dec!(0.01))];
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_INSUFFICIENT_MARGIN));
+}
+
+#[test]
+fn test_margin_order_margin_tier_details() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.margin_tiers = vec![
+        (dec!(10000), dec!(0.02), dec!(0.01)),
+        (dec!(100000), dec!(0.05), dec!(0.025)),
+    ];
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(2000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let account_details = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details.positions[0].margin_tier, 1);
+    assert_eq!(account_details.positions[0].margin_initial, trade_size_2 * dec!(0.02));
+    assert_eq!(account_details.positions[0].margin_maintenance, trade_size_2 * dec!(0.01));
+}
+
+#[test]
+fn test_margin_order_invalid_margin_tiers() {
+    let mut interface = get_setup();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.margin_tiers = vec![
+        (dec!(100000), dec!(0.05), dec!(0.025)),
+        (dec!(10000), dec!(0.02), dec!(0.01)),
+    ];
+    interface.update_pair_configs(vec![pair_config])
+        .expect_specific_failure(|err| check_error_msg(err, "Invalid margin tier value"));
+}
+
+#[test]
+fn test_margin_order_fee_model_piecewise_linear() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.fee_model = FeeModel::PiecewiseLinear(vec![
+        (dec!(0), dec!(0.001)),
+        (dec!(100000), dec!(0.002)),
+    ]);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let value = trade_size_2 * price_3;
+    let fee_0 = value * pair_config.fee_0;
+    let rate = dec!(0.001) + (dec!(0.002) - dec!(0.001)) * (value - dec!(0)) / (dec!(100000) - dec!(0));
+    let fee_1 = (dec!(0.001) + rate) / dec!(2) * (value - dec!(0));
+    let fee = fee_0 + fee_1;
+
+    let account_details = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details.positions[0].cost, value + fee);
+}
+
+#[test]
+fn test_margin_order_fee_model_sqrt() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.fee_model = FeeModel::Sqrt(dec!(0.00001));
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(1000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let value = trade_size_2 * price_3;
+    let fee_0 = value * pair_config.fee_0;
+    let fee_1 = dec!(0.00001) * dec!(2) / dec!(3) * value * value.checked_sqrt().unwrap();
+    let fee = fee_0 + fee_1;
+
+    let account_details = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details.positions[0].cost, value + fee);
+}
+
+#[test]
+fn test_margin_order_invalid_fee_model() {
+    let mut interface = get_setup();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.fee_model = FeeModel::PiecewiseLinear(vec![
+        (dec!(0), dec!(0.002)),
+        (dec!(100000), dec!(0.001)),
+    ]);
+    interface.update_pair_configs(vec![pair_config])
+        .expect_specific_failure(|err| check_error_msg(err, "Invalid fee curve rate"));
+}
+
+#[test]
+fn test_margin_order_skew_rebate() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.rebate_budget = dec!(1000);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    let mut pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    pair_config.fee_0 = dec!(0.001);
+    pair_config.skew_rebate_rate = dec!(0.0005);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1a = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_a = result_1a.new_component_addresses()[0];
+    let result_1b = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_b = result_1b.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component_a,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    let result_3 = interface.process_request(
+        margin_account_component_a,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success().clone();
+
+    let event_3: EventMarginOrder = interface.parse_event(&result_3);
+    assert_eq!(event_3.rebate, dec!(0));
+
+    let trade_size_4 = dec!(-0.5);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component_b,
+        pair_config.pair_id.clone(),
+        trade_size_4,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let pool_value_4 = interface.get_pool_value();
+    let time_5 = interface.increment_ledger_time(1);
+    let result_5 = interface.process_request(
+        margin_account_component_b,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_5,
+            },
+        ])
+    ).expect_commit_success().clone();
+    let pool_value_5 = interface.get_pool_value();
+
+    let rebate = -trade_size_4 * price_3 * pair_config.skew_rebate_rate;
+    let event_5: EventMarginOrder = interface.parse_event(&result_5);
+    assert_eq!(event_5.rebate, rebate);
+    assert_eq!(pool_value_5, pool_value_4 + event_5.fee_pool - rebate);
+
+    let account_details = interface.get_account_details(margin_account_component_b, 0, None);
+    assert_eq!(account_details.virtual_balance, rebate);
+}
+
+#[test]
+fn test_margin_order_skew_rebate_budget() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.rebate_budget = dec!(10);
+    exchange_config.rebate_period_seconds = 3600;
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    let mut pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    pair_config.fee_0 = dec!(0.001);
+    pair_config.skew_rebate_rate = dec!(0.0005);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1a = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_a = result_1a.new_component_addresses()[0];
+    let result_1b = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_b = result_1b.new_component_addresses()[0];
+
+    let price = dec!(60000);
+    let trade_sizes = vec![
+        (margin_account_component_a, dec!(1)),
+        (margin_account_component_b, dec!(-0.5)),
+        (margin_account_component_b, dec!(-0.25)),
+    ];
+    let mut rebates = vec![];
+    for (i, (margin_account_component, trade_size)) in trade_sizes.into_iter().enumerate() {
+        interface.margin_order_tp_sl_request(
+            0,
+            10000000000,
+            margin_account_component,
+            pair_config.pair_id.clone(),
+            trade_size,
+            false,
+            PriceLimit::None,
+            SlippageLimit::None,
+            None,
+            None,
+        ).expect_commit_success();
+
+        let time = interface.increment_ledger_time(1);
+        let result = interface.process_request(
+            margin_account_component,
+            if i == 2 { 1 } else { 0 }, 
+            Some(vec![
+                Price {
+                    pair: pair_config.pair_id.clone(),
+                    quote: price,
+                    timestamp: time,
+                },
+            ])
+        ).expect_commit_success().clone();
+        let event: EventMarginOrder = interface.parse_event(&result);
+        rebates.push(event.rebate);
+    }
+
+    assert_eq!(rebates, vec![dec!(0), dec!(10), dec!(0)]);
+
+    interface.increment_ledger_time(3600);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component_b,
+        pair_config.pair_id.clone(),
+        dec!(-0.25),
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time = interface.increment_ledger_time(1);
+    let result = interface.process_request(
+        margin_account_component_b,
+        2, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price,
+                timestamp: time,
+            },
+        ])
+    ).expect_commit_success().clone();
+    let event: EventMarginOrder = interface.parse_event(&result);
+    assert_eq!(event.rebate, dec!(0.25) * price * pair_config.skew_rebate_rate);
+}
+
+#[test]
+fn test_margin_order_skew_rebate_zero_fee() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.rebate_budget = dec!(1000);
+    interface.update_exchange_config(exchange_config.clone()).expect_commit_success();
+
+    let mut pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    pair_config.skew_rebate_rate = dec!(0.0005);
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1a = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_a = result_1a.new_component_addresses()[0];
+    let result_1b = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_b = result_1b.new_component_addresses()[0];
+
+    let price = dec!(60000);
+    let trade_sizes = vec![
+        (margin_account_component_a, dec!(1)),
+        (margin_account_component_b, dec!(-0.5)),
+    ];
+    let mut events = vec![];
+    for (margin_account_component, trade_size) in trade_sizes.into_iter() {
+        interface.margin_order_tp_sl_request(
+            0,
+            10000000000,
+            margin_account_component,
+            pair_config.pair_id.clone(),
+            trade_size,
+            false,
+            PriceLimit::None,
+            SlippageLimit::None,
+            None,
+            None,
+        ).expect_commit_success();
+
+        let time = interface.increment_ledger_time(1);
+        let result = interface.process_request(
+            margin_account_component,
+            0, 
+            Some(vec![
+                Price {
+                    pair: pair_config.pair_id.clone(),
+                    quote: price,
+                    timestamp: time,
+                },
+            ])
+        ).expect_commit_success().clone();
+        let event: EventMarginOrder = interface.parse_event(&result);
+        events.push(event);
+    }
+
+    let event = &events[1];
+    let fee_paid = event.fee_pool + event.fee_protocol + event.fee_treasury + event.fee_referral;
+    assert_eq!(fee_paid, dec!(0));
+    assert!(event.rebate.is_positive());
+    assert_eq!(event.rebate, dec!(0.5) * price * pair_config.skew_rebate_rate);
+
+    let account_details = interface.get_account_details(margin_account_component_b, 0, None);
+    assert_eq!(account_details.virtual_balance, event.rebate);
+}
+
+#[test]
+fn test_margin_order_invalid_skew_rebate_rate() {
+    let mut interface = get_setup();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.skew_rebate_rate = dec!(0.01);
+    interface.update_pair_configs(vec![pair_config])
+        .expect_specific_failure(|err| check_error_msg(err, "Invalid skew rebate rate"));
+}
+
+#[test]
+fn test_margin_order_price_confidence() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1a = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_a = result_1a.new_component_addresses()[0];
+    let result_1b = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component_b = result_1b.new_component_addresses()[0];
+
+    let trade_size_2a = dec!(1);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component_a,
+        pair_config.pair_id.clone(),
+        trade_size_2a,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    let trade_size_2b = dec!(-0.5);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component_b,
+        pair_config.pair_id.clone(),
+        trade_size_2b,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let confidence_3 = dec!(30);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request_versioned(
+        margin_account_component_a,
+        0, 
+        VersionedPrices::V2(vec![
+            PriceV2 {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                confidence: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_CONFIDENCE));
+    let result_3a = interface.process_request_versioned(
+        margin_account_component_a,
+        0, 
+        VersionedPrices::V2(vec![
+            PriceV2 {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                confidence: confidence_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success().clone();
+    let result_3b = interface.process_request_versioned(
+        margin_account_component_b,
+        0, 
+        VersionedPrices::V2(vec![
+            PriceV2 {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                confidence: confidence_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success().clone();
+
+    let event_3a: EventMarginOrder = interface.parse_event(&result_3a);
+    assert_eq!(event_3a.price, price_3 + confidence_3);
+    let event_3b: EventMarginOrder = interface.parse_event(&result_3b);
+    assert_eq!(event_3b.price, price_3 - confidence_3);
+
+    let account_details_a = interface.get_account_details(margin_account_component_a, 0, None);
+    assert_eq!(account_details_a.positions[0].cost, trade_size_2a * (price_3 + confidence_3));
+    let account_details_b = interface.get_account_details(margin_account_component_b, 0, None);
+    assert_eq!(account_details_b.positions[0].cost, trade_size_2b * (price_3 - confidence_3));
+}
+
+#[test]
+fn test_margin_order_limit_traded_through() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let time_2 = interface.increment_ledger_time(1);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(59800),
+                timestamp: time_2,
+            },
+        ])
+    ).expect_commit_success();
+
+    interface.increment_ledger_time(1);
+    let trade_size_3 = dec!(0.1);
+    let price_limit_3 = dec!(60000);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_3,
+        false,
+        PriceLimit::Lte(price_limit_3),
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time_4 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60100),
+                timestamp: time_4,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_MARGIN_ORDER_PRICE_LIMIT));
+
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(59900),
+                timestamp: time_4,
+            },
+        ])
+    ).expect_commit_success();
+
+    let time_5 = interface.increment_ledger_time(1);
+    let result_5 = interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60100),
+                timestamp: time_5,
+            },
+        ])
+    ).expect_commit_success().clone();
+
+    let event_5: EventMarginOrder = interface.parse_event(&result_5);
+    assert_eq!(event_5.price, price_limit_3);
+
+    let account_details = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details.positions[0].cost, trade_size_3 * price_limit_3);
+}
+
+#[test]
+fn test_margin_order_limit_traded_through_ask() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = pair_config_zero_fees_and_funding("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_1)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    interface.increment_ledger_time(1);
+    let trade_size_2 = dec!(0.1);
+    let price_limit_2 = dec!(60000);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::Lte(price_limit_2),
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time_3 = interface.increment_ledger_time(1);
+    let price_data_3 = scrypto_encode(&VersionedPrices::V2(vec![
+        PriceV2 {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(59950),
+            confidence: dec!(100),
+            timestamp: time_3,
+        },
+    ])).unwrap();
+    interface.update_pairs_with_data(vec![pair_config.pair_id.clone()], price_data_3).expect_commit_success();
+
+    let time_4 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60100),
+                timestamp: time_4,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_MARGIN_ORDER_PRICE_LIMIT));
+
+    let price_data_4 = scrypto_encode(&VersionedPrices::V2(vec![
+        PriceV2 {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(59900),
+            confidence: dec!(50),
+            timestamp: time_4,
+        },
+    ])).unwrap();
+    interface.update_pairs_with_data(vec![pair_config.pair_id.clone()], price_data_4).expect_commit_success();
+
+    let time_5 = interface.increment_ledger_time(1);
+    let result_5 = interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60100),
+                timestamp: time_5,
+            },
+        ])
+    ).expect_commit_success().clone();
+
+    let event_5: EventMarginOrder = interface.parse_event(&result_5);
+    assert_eq!(event_5.price, price_limit_2);
+}
+
 #[test]
 fn test_margin_order_exceed_positions_max() {
     let mut interface = get_setup();
@@ -460,6 +460,17 @@ fn test_margin_order_tp_sl_min_trade_size_not_met_long() {
         funding_share: dec!(0.02),
         fee_0: dec!(0.0005),
         fee_1: dec!(0.0000000005),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     };
     interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
 
@@ -507,6 +518,17 @@ fn test_margin_order_tp_sl_min_trade_size_not_met_short() {
         funding_share: dec!(0.02),
         fee_0: dec!(0.0005),
         fee_1: dec!(0.0000000005),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     };
     interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
 
@@ -483,3 +483,175 @@ fn test_process_request_before_valid_requests_start() {
         ])
     ).expect_specific_failure(|err| check_error_msg(err, ERROR_PROCESS_REQUEST_BEFORE_VALID_START));
 }
+
+#[test]
+fn test_process_request_price_before_submission() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let btc_resource = interface.mint_test_token(dec!(100), 8);
+    
+    let collateral_config = CollateralConfig {
+        pair_id: "BTC/USD".to_string(),
+        price_age_max: 5,
+        discount: dec!(0.90),
+        margin: dec!(0.01),
+    };
+    interface.update_collateral_configs(vec![
+        (btc_resource, collateral_config.clone()),
+    ]).expect_commit_success();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_1 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_1)).expect_commit_success();
+
+    let base_input_2 = dec!(10000);
+    let btc_input_2 = dec!(0.1);
+    let result_2 = interface.create_account(
+        rule!(allow_all), 
+        vec![
+            (base_resource, base_input_2),
+            (btc_resource, btc_input_2),
+        ], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_2.new_component_addresses()[0];
+
+    let time_3 = interface.increment_ledger_time(1);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60000),
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let trade_size_4 = dec!(0.01);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_4,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    let target_account_4 = interface.test_account;
+    interface.remove_collateral_request(
+        10000000000,
+        margin_account_component,
+        target_account_4,
+        vec![(btc_resource, dec!(0.01))],
+    ).expect_commit_success();
+
+    let time_5 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        None,
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PRICE_BEFORE_SUBMISSION));
+    interface.process_request(
+        margin_account_component,
+        1, 
+        None,
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PRICE_BEFORE_SUBMISSION));
+
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60000),
+                timestamp: time_5,
+            },
+        ])
+    ).expect_commit_success();
+    interface.process_request(
+        margin_account_component,
+        1, 
+        None,
+    ).expect_commit_success();
+}
+
+#[test]
+fn test_process_request_price_before_submission_unused_pair() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+    let eth_resource = interface.mint_test_token(dec!(100), 8);
+    
+    let collateral_config = CollateralConfig {
+        pair_id: "ETH/USD".to_string(),
+        price_age_max: 5,
+        discount: dec!(0.90),
+        margin: dec!(0.01),
+    };
+    interface.update_collateral_configs(vec![
+        (eth_resource, collateral_config.clone()),
+    ]).expect_commit_success();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_1 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_1)).expect_commit_success();
+
+    let base_input_2 = dec!(10000);
+    let result_2 = interface.create_account(
+        rule!(allow_all), 
+        vec![(base_resource, base_input_2)], 
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_2.new_component_addresses()[0];
+
+    let time_3 = interface.increment_ledger_time(1);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60000),
+                timestamp: time_3,
+            },
+            Price {
+                pair: collateral_config.pair_id.clone(),
+                quote: dec!(3000),
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let trade_size_4 = dec!(0.01);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_4,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time_5 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0, 
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60000),
+                timestamp: time_5,
+            },
+        ])
+    ).expect_commit_success();
+}
@@ -0,0 +1,198 @@
+#[path = "tests_common/mod.rs"]
+mod tests_common;
+use tests_common::*;
+
+#[test]
+fn test_remove_pair_config() {
+    let mut interface = get_setup();
+    let len_0 = interface.get_pair_configs_len();
+
+    let pair_configs_1 = vec![
+        default_pair_config("BTC/USD".into()),
+        default_pair_config("ETH/USD".into()),
+        default_pair_config("SOL/USD".into()),
+    ];
+    interface.update_pair_configs(pair_configs_1).expect_commit_success();
+    assert_eq!(interface.get_pair_configs_len(), len_0 + 3);
+
+    let pair_config_2 = PairConfig {
+        status: PairStatus::Halted,
+        ..default_pair_config("ETH/USD".into())
+    };
+    interface.update_pair_configs(vec![pair_config_2]).expect_commit_success();
+    let result_2 = interface.remove_pair_config("ETH/USD".into()).expect_commit_success().clone();
+    let event: EventPairConfigRemoval = interface.parse_event(&result_2);
+    assert_eq!(event.pair_id, "ETH/USD".to_string());
+
+    let len_2 = interface.get_pair_configs_len();
+    assert_eq!(len_2, len_0 + 2);
+    let pair_ids_2: Vec<PairId> = interface.get_pair_configs(len_2, None).into_iter().map(|config| config.pair_id).collect();
+    assert_eq!(pair_ids_2.len() as ListIndex, len_2);
+    assert!(pair_ids_2.contains(&"BTC/USD".to_string()));
+    assert!(pair_ids_2.contains(&"SOL/USD".to_string()));
+    assert!(!pair_ids_2.contains(&"ETH/USD".to_string()));
+
+    interface.update_pair_configs(vec![default_pair_config("ETH/USD".into())]).expect_commit_success();
+    assert_eq!(interface.get_pair_configs_len(), len_0 + 3);
+}
+
+#[test]
+fn test_remove_pair_config_open_interest() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, base_input_1)],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.01);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        -trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time_3 = interface.increment_ledger_time(1);
+    let prices_3 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_3,
+        },
+    ];
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(prices_3.clone()),
+    ).expect_commit_success();
+
+    let pair_config_halted = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_halted.clone()]).expect_commit_success();
+    interface.remove_pair_config(pair_config.pair_id.clone())
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_OI_NOT_ZERO));
+
+    let pair_config_reduce_only = PairConfig {
+        status: PairStatus::ReduceOnly,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_reduce_only]).expect_commit_success();
+    interface.process_request(
+        margin_account_component,
+        1,
+        Some(prices_3.clone()),
+    ).expect_commit_success();
+    let pool_position_4 = interface.get_pool_position(pair_config.pair_id.clone());
+    assert!(pool_position_4.mark_price.is_positive());
+
+    interface.update_pair_configs(vec![pair_config_halted]).expect_commit_success();
+    let len_5 = interface.get_pair_configs_len();
+    interface.remove_pair_config(pair_config.pair_id.clone()).expect_commit_success();
+    assert_eq!(interface.get_pair_configs_len(), len_5 - 1);
+
+    let pool_position_5 = interface.get_pool_position(pair_config.pair_id.clone());
+    assert_eq!(pool_position_5.oi_long, dec!(0));
+    assert_eq!(pool_position_5.oi_short, dec!(0));
+    assert_eq!(pool_position_5.cost, dec!(0));
+    assert_eq!(pool_position_5.pnl_snap, dec!(0));
+    assert_eq!(pool_position_5.funding_long_index, dec!(0));
+    assert_eq!(pool_position_5.funding_short_index, dec!(0));
+    assert_eq!(pool_position_5.mark_price, dec!(0));
+
+    let history_5 = interface.get_pair_history(pair_config.pair_id.clone(), time_3, time_3);
+    assert!(history_5.is_empty());
+}
+
+#[test]
+fn test_remove_pair_config_status() {
+    let mut interface = get_setup();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    interface.remove_pair_config(pair_config.pair_id.clone())
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_NOT_HALTED));
+
+    let pair_config_halted = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_halted]).expect_commit_success();
+    let result_1 = interface.schedule_remove_pair_config(pair_config.pair_id.clone()).expect_commit_success().clone();
+    let event_1: EventConfigUpdateScheduled = interface.parse_event(&result_1);
+
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+    let delay = interface.get_exchange_config().config_delay_pairs;
+    interface.increment_ledger_time(delay);
+    interface.apply_config_updates(vec![event_1.id])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_NOT_HALTED));
+    assert_eq!(interface.get_pending_config_updates().len(), 1);
+}
+
+#[test]
+fn test_remove_pair_config_cancels_pending_pair_updates() {
+    let mut interface = get_setup();
+
+    let pair_config_0 = default_pair_config("BTC/USD".into());
+    let pair_config_1 = default_pair_config("ETH/USD".into());
+    interface.update_pair_configs(vec![pair_config_0.clone(), pair_config_1.clone()]).expect_commit_success();
+
+    let pair_config_2 = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config_0.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_2]).expect_commit_success();
+
+    let result_3 = interface.schedule_pair_configs(vec![pair_config_0.clone()]).expect_commit_success().clone();
+    let event_3: EventConfigUpdateScheduled = interface.parse_event(&result_3);
+    let result_4 = interface.schedule_pair_configs(vec![pair_config_1.clone()]).expect_commit_success().clone();
+    let event_4: EventConfigUpdateScheduled = interface.parse_event(&result_4);
+
+    let result_5 = interface.remove_pair_config(pair_config_0.pair_id.clone()).expect_commit_success().clone();
+    let event_5: EventConfigUpdateCancelled = interface.parse_event(&result_5);
+    assert_eq!(event_5.id, event_3.id);
+
+    let pending_updates = interface.get_pending_config_updates();
+    assert_eq!(pending_updates.len(), 1);
+    assert_eq!(pending_updates[0].0, event_4.id);
+
+    let delay = interface.get_exchange_config().config_delay_pairs;
+    interface.increment_ledger_time(delay);
+    interface.apply_config_updates(vec![event_3.id])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_CONFIG_UPDATE));
+    interface.apply_config_updates(vec![event_4.id]).expect_commit_success();
+
+    let pair_ids: Vec<PairId> = interface.get_pair_configs(10, None).into_iter().map(|config| config.pair_id).collect();
+    assert_eq!(pair_ids, vec![pair_config_1.pair_id.clone()]);
+}
@@ -0,0 +1,305 @@
+#[path = "tests_common/mod.rs"]
+mod tests_common;
+use tests_common::*;
+
+#[test]
+fn test_settle_position() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, base_input_1)],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.01);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    interface.settle_position(
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        None,
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_NOT_SETTLED));
+
+    let settlement_price_4 = dec!(70000);
+    let pair_config_4 = PairConfig {
+        status: PairStatus::Settled(settlement_price_4),
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_4]).expect_commit_success();
+
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        -trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        1,
+        None,
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_DISABLED));
+
+    let result_5 = interface.settle_position(
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        None,
+    ).expect_commit_success().clone();
+
+    let event: EventSettlePosition = interface.parse_event(&result_5);
+    assert_eq!(event.account, margin_account_component);
+    assert_eq!(event.pair_id, pair_config.pair_id);
+    assert_eq!(event.price, settlement_price_4);
+    assert_eq!(event.amount_close, -trade_size_2);
+
+    let account_details_5 = interface.get_account_details(margin_account_component, 0, None);
+    assert_eq!(account_details_5.positions.len(), 0);
+
+    interface.settle_position(
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        None,
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_SETTLE_NO_POSITION));
+}
+
+#[test]
+fn test_settle_position_funding_frozen() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, base_input_1)],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.01);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let settlement_price_4 = dec!(70000);
+    let pair_config_4 = PairConfig {
+        status: PairStatus::Settled(settlement_price_4),
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_4]).expect_commit_success();
+    let pool_position_4 = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].pool_position.clone();
+
+    interface.increment_ledger_time(3600);
+    interface.update_pairs(vec![pair_config.pair_id.clone()], None).expect_commit_success();
+
+    let pool_position_5 = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].pool_position.clone();
+    assert_eq!(pool_position_5.last_update, pool_position_4.last_update);
+    assert_eq!(pool_position_5.funding_2_rate, pool_position_4.funding_2_rate);
+    assert_eq!(pool_position_5.funding_long_index, pool_position_4.funding_long_index);
+
+    interface.increment_ledger_time(3600);
+    let result_6 = interface.settle_position(
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        None,
+    ).expect_commit_success().clone();
+
+    let event_6: EventSettlePosition = interface.parse_event(&result_6);
+    assert_eq!(event_6.price, settlement_price_4);
+    assert_eq!(event_6.funding, dec!(0));
+
+    let pool_position_6 = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].pool_position.clone();
+    assert_eq!(pool_position_6.last_update, pool_position_4.last_update);
+    assert_eq!(pool_position_6.funding_2_rate, pool_position_4.funding_2_rate);
+}
+
+#[test]
+fn test_pair_status_reduce_only_and_halted() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let base_input_1 = dec!(10000);
+    let result_1 = interface.create_account(
+        rule!(allow_all),
+        vec![(base_resource, base_input_1)],
+        None,
+    ).expect_commit_success().clone();
+    let margin_account_component = result_1.new_component_addresses()[0];
+
+    let trade_size_2 = dec!(0.01);
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let price_3 = dec!(60000);
+    let time_3 = interface.increment_ledger_time(1);
+    interface.process_request(
+        margin_account_component,
+        0,
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_3,
+                timestamp: time_3,
+            },
+        ])
+    ).expect_commit_success();
+
+    let pair_config_4 = PairConfig {
+        status: PairStatus::ReduceOnly,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_4]).expect_commit_success();
+
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        trade_size_2,
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+    interface.margin_order_tp_sl_request(
+        0,
+        10000000000,
+        margin_account_component,
+        pair_config.pair_id.clone(),
+        -trade_size_2 / dec!(2),
+        false,
+        PriceLimit::None,
+        SlippageLimit::None,
+        None,
+        None,
+    ).expect_commit_success();
+
+    let time_5 = interface.increment_ledger_time(1);
+    let prices_5 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: price_3,
+            timestamp: time_5,
+        },
+    ];
+    interface.process_request(
+        margin_account_component,
+        1,
+        Some(prices_5.clone()),
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_REDUCE_ONLY));
+
+    let pair_config_6 = PairConfig {
+        status: PairStatus::Halted,
+        ..pair_config.clone()
+    };
+    interface.update_pair_configs(vec![pair_config_6]).expect_commit_success();
+
+    interface.process_request(
+        margin_account_component,
+        2,
+        Some(prices_5.clone()),
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_PAIR_DISABLED));
+    interface.liquidate(
+        margin_account_component,
+        (base_resource, dec!(1000)),
+        Some(prices_5.clone()),
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_LIQUIDATION_SUFFICIENT_MARGIN));
+    interface.cancel_requests(
+        margin_account_component,
+        vec![2],
+    ).expect_commit_success();
+
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    interface.process_request(
+        margin_account_component,
+        1,
+        Some(prices_5.clone()),
+    ).expect_commit_success();
+}
@@ -1169,4 +1169,577 @@ fn test_update_pairs_no_oi_short() {
     assert_eq!(update.last_price, price_2);
 }
 
+#[test]
+fn test_update_pairs_funding_preview() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let amount_long_1 = dec!(1);
+    let amount_short_1 = dec!(0.7);
+    let price_1 = dec!(60000);
+    interface.make_open_interest(pair_config.pair_id.clone(), amount_long_1, amount_short_1, price_1);
+
+    let pool_position_2 = interface.get_pool_position(pair_config.pair_id.clone());
+    let price_2 = dec!(65000);
+    let time_2 = interface.increment_ledger_time(10000);
+    let prices_2 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: price_2,
+            timestamp: time_2,
+        },
+    ];
+    let preview = interface.get_pair_funding_preview(
+        vec![pair_config.pair_id.clone()], 
+        hashmap!(pair_config.pair_id.clone() => price_2),
+    )[0].clone();
+    
+    let pool_position_3 = interface.get_pool_position(pair_config.pair_id.clone());
+    assert_eq!(pool_position_3.funding_2_rate, pool_position_2.funding_2_rate);
+    assert_eq!(pool_position_3.funding_long_index, pool_position_2.funding_long_index);
+    assert_eq!(pool_position_3.last_update, pool_position_2.last_update);
+    assert_eq!(pool_position_3.last_price, pool_position_2.last_price);
+
+    interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_2)).expect_commit_success();
+    let pool_position_4 = interface.get_pool_position(pair_config.pair_id.clone());
+
+    assert_eq!(preview.pair_id, pair_config.pair_id);
+    assert_eq!(preview.price, price_2);
+    assert_eq!(preview.funding_2_rate, pool_position_4.funding_2_rate);
+    assert_eq!(preview.funding_long_index, pool_position_4.funding_long_index);
+    assert_eq!(preview.funding_short_index, pool_position_4.funding_short_index);
+
+    let oi_value = (amount_long_1 + amount_short_1) * price_2;
+    let skew = (amount_long_1 - amount_short_1) * price_2;
+    let funding_2_max = amount_long_1 * price_2;
+    let funding_2_min = -amount_short_1 * price_2;
+    let funding_rate = skew * pair_config.funding_1 + pool_position_4.funding_2_rate.clamp(funding_2_min, funding_2_max) * pair_config.funding_2;
+    let funding_share_rate = funding_rate * pair_config.funding_share;
+    let funding_pool_rate = oi_value * pair_config.funding_pool_0 + skew * pair_config.funding_pool_1;
+    
+    assert_eq!(preview.funding_long_rate, funding_rate / (amount_long_1 * price_2) + funding_pool_rate / oi_value);
+    assert_eq!(preview.funding_short_rate, -(funding_rate - funding_share_rate) / (amount_short_1 * price_2) + funding_pool_rate / oi_value);
+    assert_eq!(preview.funding_pool_rate, (funding_pool_rate + funding_share_rate) / oi_value);
+}
+
+#[test]
+fn test_update_pairs_pair_history() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let amount_long_1 = dec!(1);
+    let amount_short_1 = dec!(0.7);
+    let price_1 = dec!(60000);
+    interface.make_open_interest(pair_config.pair_id.clone(), amount_long_1, amount_short_1, price_1);
+
+    let mut pool_positions = vec![];
+    let mut times = vec![];
+    for price in [dec!(65000), dec!(62000)] {
+        let time = interface.increment_ledger_time(10000);
+        interface.update_pairs(
+            vec![pair_config.pair_id.clone()],
+            Some(vec![
+                Price {
+                    pair: pair_config.pair_id.clone(),
+                    quote: price,
+                    timestamp: time,
+                },
+            ])
+        ).expect_commit_success();
+        pool_positions.push(interface.get_pool_position(pair_config.pair_id.clone()));
+        times.push(time);
+    }
+
+    let history = interface.get_pair_history(pair_config.pair_id.clone(), times[0], times[1]);
+    assert_eq!(history.len(), 2);
+    for (checkpoint, pool_position) in history.iter().zip(pool_positions.iter()) {
+        assert_eq!(checkpoint.oi_long, pool_position.oi_long);
+        assert_eq!(checkpoint.oi_short, pool_position.oi_short);
+        assert_eq!(checkpoint.funding_2_rate, pool_position.funding_2_rate);
+        assert_eq!(checkpoint.funding_long_index, pool_position.funding_long_index);
+        assert_eq!(checkpoint.funding_short_index, pool_position.funding_short_index);
+        assert_eq!(checkpoint.last_update, pool_position.last_update);
+        assert_eq!(checkpoint.last_price, pool_position.last_price);
+    }
+
+    let history = interface.get_pair_history(pair_config.pair_id.clone(), times[1], times[1]);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].last_price, dec!(62000));
+}
+
+#[test]
+fn test_update_pairs_pair_history_ring() {
+    let mut interface = get_setup();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let mut times = vec![];
+    for (price, increment) in [(dec!(60000), 1), (dec!(61000), 2160 * 3600)] {
+        let time = interface.increment_ledger_time(increment);
+        interface.update_pairs(
+            vec![pair_config.pair_id.clone()],
+            Some(vec![
+                Price {
+                    pair: pair_config.pair_id.clone(),
+                    quote: price,
+                    timestamp: time,
+                },
+            ])
+        ).expect_commit_success();
+        times.push(time);
+    }
+
+    let history = interface.get_pair_history(pair_config.pair_id.clone(), times[0], times[0]);
+    assert_eq!(history.len(), 0);
+
+    let history = interface.get_pair_history(pair_config.pair_id.clone(), times[1], times[1]);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history[0].last_price, dec!(61000));
+}
+
+#[test]
+fn test_update_pairs_premium_funding() {
+    let mut interface = get_setup();
+    let base_resource = interface.resources.base_resource;
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let base_input_0 = dec!(1000000);
+    interface.add_liquidity((base_resource, base_input_0)).expect_commit_success();
+
+    let amount_long_1 = dec!(1);
+    let amount_short_1 = dec!(0.7);
+    let price_1 = dec!(60000);
+    interface.make_open_interest(pair_config.pair_id.clone(), amount_long_1, amount_short_1, price_1);
+    let time_1 = interface.ledger_time();
+
+    let premium = PremiumFunding {
+        mark_pair_id: "BTC/USD-PERP".into(),
+        funding_premium: dec!(100),
+        premium_dampener: dec!(0.0005),
+        premium_max: dec!(0.005),
+    };
+    pair_config.funding_model = FundingModel::Premium(premium.clone());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let pool_details_2 = interface.get_pool_details();
+    let pool_position_2 = interface.get_pool_position(pair_config.pair_id.clone());
+    let price_2 = dec!(65000);
+    let mark_price_2 = dec!(65650);
+    let time_2 = interface.increment_ledger_time(10000);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: price_2,
+                timestamp: time_2,
+            },
+            Price {
+                pair: premium.mark_pair_id.clone(),
+                quote: mark_price_2,
+                timestamp: time_2,
+            },
+        ])
+    ).expect_commit_success();
+
+    let oi_net = amount_long_1 + amount_short_1;
+    let skew_abs = ((amount_long_1 - amount_short_1) * price_2).checked_abs().unwrap();
+
+    let period = period(time_2, time_1);
+    let premium_index = (mark_price_2 - price_2) / price_2;
+    let premium_rate = (premium_index - premium.premium_dampener).min(premium.premium_max) * premium.funding_premium;
+    let funding_long = premium_rate * amount_long_1 * price_2 * period;
+    let funding_share = funding_long * pair_config.funding_share;
+    let funding_short = -(funding_long - funding_share);
+
+    let funding_pool_rate = oi_net * price_2 * pair_config.funding_pool_0 + skew_abs * pair_config.funding_pool_1;
+    let funding_pool = funding_pool_rate * period;
+    let funding_pool_index = funding_pool / oi_net;
+
+    let pool_details = interface.get_pool_details();
+    assert_eq!(pool_details.unrealized_pool_funding, pool_details_2.unrealized_pool_funding + funding_pool + funding_share);
+
+    let pool_position = interface.get_pool_position(pair_config.pair_id.clone());
+    assert_eq!(pool_position.funding_long_index, pool_position_2.funding_long_index + funding_long / amount_long_1 + funding_pool_index);
+    assert_eq!(pool_position.funding_short_index, pool_position_2.funding_short_index + funding_short / amount_short_1 + funding_pool_index);
+}
+
+#[test]
+fn test_update_pairs_premium_funding_missing_mark_price() {
+    let mut interface = get_setup();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.funding_model = FundingModel::Premium(PremiumFunding {
+        mark_pair_id: "BTC/USD-PERP".into(),
+        funding_premium: dec!(100),
+        premium_dampener: dec!(0.0005),
+        premium_max: dec!(0.005),
+    });
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    interface.update_pairs(
+        vec![pair_config.pair_id.clone()],
+        Some(vec![
+            Price {
+                pair: pair_config.pair_id.clone(),
+                quote: dec!(60000),
+                timestamp: time_1,
+            },
+        ])
+    ).expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_PAIR));
+}
+
+#[test]
+fn test_update_pairs_invalid_premium_funding() {
+    let mut interface = get_setup();
+
+    let mut pair_config = default_pair_config("BTC/USD".into());
+    pair_config.funding_model = FundingModel::Premium(PremiumFunding {
+        mark_pair_id: pair_config.pair_id.clone(),
+        funding_premium: dec!(100),
+        premium_dampener: dec!(0.0005),
+        premium_max: dec!(0.005),
+    });
+    interface.update_pair_configs(vec![pair_config])
+        .expect_specific_failure(|err| check_error_msg(err, "Invalid funding mark pair"));
+}
+
+#[test]
+fn test_update_pairs_oracle_threshold() {
+    let mut interface = get_setup();
+    let oracle_key_seed = interface.components.oracle_key_seed;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    interface.oracle_add_key(1, 2).expect_commit_success();
+    interface.oracle_add_key(2, 3).expect_commit_success();
+    interface.oracle_update_threshold(2).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    let prices_1 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_1,
+        },
+    ];
+    interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_1.clone()))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INSUFFICIENT_SIGNERS));
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_1.clone(), vec![(0, oracle_key_seed), (0, oracle_key_seed)])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_DUPLICATE_KEY_ID));
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_1.clone(), vec![(0, oracle_key_seed), (1, 3)])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_1.clone(), vec![(0, oracle_key_seed), (2, 3)])
+        .expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(60000));
+}
+
+#[test]
+fn test_update_pairs_oracle_threshold_reachable() {
+    let mut interface = get_setup();
+
+    interface.oracle_add_key(1, 2).expect_commit_success();
+    interface.oracle_add_key(2, 3).expect_commit_success();
+    interface.oracle_update_threshold(4)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_THRESHOLD_UNREACHABLE));
+    interface.oracle_update_threshold(2).expect_commit_success();
+
+    let rotation_time = interface.increment_ledger_time(1);
+    interface.oracle_update_key_validity(2, None, Some(rotation_time), None).expect_commit_success();
+    interface.oracle_update_threshold(3)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_THRESHOLD_UNREACHABLE));
+
+    interface.oracle_remove_key(1)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_THRESHOLD_UNREACHABLE));
+    interface.oracle_update_threshold(1).expect_commit_success();
+    interface.oracle_remove_key(1).expect_commit_success();
+}
+
+#[test]
+fn test_update_pairs_oracle_key_rotation() {
+    let mut interface = get_setup();
+    let oracle_key_seed = interface.components.oracle_key_seed;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let rotation_time = interface.increment_ledger_time(0).add_seconds(10).unwrap();
+    interface.oracle_add_key(1, 2).expect_commit_success();
+    interface.oracle_update_key_validity(1, Some(rotation_time), Some(rotation_time), None)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_KEY_VALIDITY));
+    interface.oracle_update_key_validity(2, Some(rotation_time), None, None)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_KEY_ID));
+    interface.oracle_update_key_validity(0, None, Some(rotation_time), None).expect_commit_success();
+    interface.oracle_update_key_validity(1, Some(rotation_time), None, None).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    let prices_1 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_1,
+        },
+    ];
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_1.clone(), vec![(1, 2)])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_KEY_NOT_VALID));
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_1, vec![(0, oracle_key_seed)])
+        .expect_commit_success();
+
+    let time_2 = interface.increment_ledger_time(9);
+    let prices_2 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(61000),
+            timestamp: time_2,
+        },
+    ];
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_2.clone(), vec![(0, oracle_key_seed)])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_KEY_NOT_VALID));
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_2, vec![(1, 2)])
+        .expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(61000));
+
+    interface.oracle_update_key_validity(1, Some(rotation_time), None, Some(hashset!("ETH/USD".into()))).expect_commit_success();
+
+    let time_3 = interface.increment_ledger_time(1);
+    let prices_3 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(62000),
+            timestamp: time_3,
+        },
+    ];
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_3.clone(), vec![(1, 2)])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_KEY_PAIR_NOT_ALLOWED));
+
+    interface.oracle_update_key_validity(1, Some(rotation_time), None, Some(hashset!("ETH/USD".into(), pair_config.pair_id.clone()))).expect_commit_success();
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_3, vec![(1, 2)])
+        .expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(62000));
+}
+
+#[test]
+fn test_update_pairs_oracle_signature_schemes() {
+    let mut interface = get_setup();
+    let oracle_key_seed = interface.components.oracle_key_seed;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let ed25519_key = Ed25519PrivateKey::from_u64(2).unwrap();
+    let secp256k1_key = Secp256k1PrivateKey::from_u64(3).unwrap();
+    interface.oracle_add_oracle_key(1, OracleKey::Ed25519(ed25519_key.public_key())).expect_commit_success();
+    interface.oracle_add_oracle_key(2, OracleKey::Secp256k1(secp256k1_key.public_key())).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    let prices_1 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_1,
+        },
+    ];
+    let price_data_1 = scrypto_encode(&prices_1).unwrap();
+    let price_data_hash_1 = keccak256_hash(&price_data_1);
+    let bls_signature_1 = OracleSignature::Bls12381(Bls12381G1PrivateKey::from_u64(oracle_key_seed).unwrap().sign_v1(price_data_hash_1.as_ref()));
+    let ed25519_signature_1 = OracleSignature::Ed25519(vec![ed25519_key.sign(&price_data_hash_1)]);
+
+    interface.update_pairs_with_signature(vec![pair_config.pair_id.clone()], price_data_1.clone(), bls_signature_1, vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_SIGNATURE_SCHEME_MISMATCH));
+    interface.update_pairs_with_signature(vec![pair_config.pair_id.clone()], price_data_1.clone(), ed25519_signature_1.clone(), vec![2])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_SIGNATURE_SCHEME_MISMATCH));
+    interface.update_pairs_with_signature(vec![pair_config.pair_id.clone()], price_data_1.clone(), OracleSignature::Ed25519(vec![]), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    interface.update_pairs_with_signature(vec![pair_config.pair_id.clone()], price_data_1, ed25519_signature_1, vec![1])
+        .expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(60000));
+
+    let time_2 = interface.increment_ledger_time(1);
+    let prices_2 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(61000),
+            timestamp: time_2,
+        },
+    ];
+    let price_data_2 = scrypto_encode(&prices_2).unwrap();
+    let price_data_hash_2 = keccak256_hash(&price_data_2);
+    let secp256k1_signature_2 = OracleSignature::Secp256k1(vec![secp256k1_key.sign(&price_data_hash_2)]);
+    let secp256k1_signature_invalid = OracleSignature::Secp256k1(vec![Secp256k1PrivateKey::from_u64(4).unwrap().sign(&price_data_hash_2)]);
+
+    interface.update_pairs_with_signature(vec![pair_config.pair_id.clone()], price_data_2.clone(), secp256k1_signature_invalid, vec![2])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    interface.update_pairs_with_signature(vec![pair_config.pair_id.clone()], price_data_2, secp256k1_signature_2, vec![2])
+        .expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(61000));
+}
+
+#[test]
+fn test_update_pairs_oracle_agreement() {
+    let mut interface = get_setup();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let oracle_component = interface.create_oracle_component(2);
+    let mut exchange_config = interface.get_exchange_config();
+    exchange_config.oracle_components = vec![oracle_component];
+    interface.update_exchange_config(exchange_config).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    let prices_1 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_1,
+        },
+    ];
+    interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_1.clone()))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_PAIR));
+
+    let prices_oracle_1 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60100),
+            timestamp: time_1,
+        },
+    ];
+    interface.oracle_push_prices(oracle_component, prices_oracle_1, 2).expect_commit_success();
+    interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_1)).expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(60050));
+
+    let time_2 = interface.increment_ledger_time(1);
+    let prices_2 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_2,
+        },
+    ];
+    let prices_oracle_2 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(61000),
+            timestamp: time_2,
+        },
+    ];
+    interface.oracle_push_prices(oracle_component, prices_oracle_2, 2).expect_commit_success();
+    interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_2))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_PRICE_DISAGREEMENT));
+}
+
+#[test]
+fn test_update_pairs_oracle_deviation_quarantine() {
+    let mut interface = get_setup();
+    let oracle_key_seed = interface.components.oracle_key_seed;
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    interface.oracle_add_key(1, 2).expect_commit_success();
+    interface.oracle_update_deviation_rate(pair_config.pair_id.clone(), Some(dec!(0))).expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_DEVIATION_RATE));
+    interface.oracle_update_deviation_rate(pair_config.pair_id.clone(), Some(dec!(0.001))).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    let prices_1 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(60000),
+            timestamp: time_1,
+        },
+    ];
+    interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_1)).expect_commit_success();
+
+    let time_2 = interface.increment_ledger_time(1);
+    let prices_2 = vec![
+        Price {
+            pair: pair_config.pair_id.clone(),
+            quote: dec!(66000),
+            timestamp: time_2,
+        },
+    ];
+    let result = interface.update_pairs(vec![pair_config.pair_id.clone()], Some(prices_2.clone())).expect_commit_success().clone();
+    let event: EventPriceQuarantined = interface.parse_event(&result);
+    assert_eq!(event.pair_id, pair_config.pair_id);
+    assert_eq!(event.quote, dec!(66000));
+    assert_eq!(event.last_quote, dec!(60000));
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(60000));
+
+    interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_2.clone(), vec![(0, oracle_key_seed)]).expect_commit_success();
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(60000));
+
+    let result = interface.update_pairs_multisig(vec![pair_config.pair_id.clone()], prices_2, vec![(1, 2)]).expect_commit_success().clone();
+    let event: EventPriceConfirmed = interface.parse_event(&result);
+    assert_eq!(event.quote, dec!(66000));
+    assert_eq!(event.key_ids, vec![1]);
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(66000));
+}
+
+#[test]
+fn test_update_pairs_compact_prices() {
+    let mut interface = get_setup();
+
+    let pair_config = default_pair_config("BTC/USD".into());
+    interface.update_pair_configs(vec![pair_config.clone()]).expect_commit_success();
+
+    let time_1 = interface.increment_ledger_time(1);
+    let encode = |index: u16, timestamp_delta: u16, quote: u64| -> Vec<u8> {
+        let mut price_data = vec![COMPACT_PRICES_PREFIX];
+        price_data.extend((time_1.seconds_since_unix_epoch - 1).to_be_bytes());
+        price_data.extend(index.to_be_bytes());
+        price_data.extend(timestamp_delta.to_be_bytes());
+        price_data.extend(quote.to_be_bytes());
+        price_data
+    };
+
+    interface.update_pairs_with_data(vec![pair_config.pair_id.clone()], encode(7, 1, 6000050000000))
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_PAIR_INDEX));
+
+    interface.oracle_update_pair_index(7, Some(pair_config.pair_id.clone())).expect_commit_success();
+    let mut invalid_data = encode(7, 1, 6000050000000);
+    invalid_data.pop();
+    interface.update_pairs_with_data(vec![pair_config.pair_id.clone()], invalid_data)
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_DATA));
+    interface.update_pairs_with_data(vec![pair_config.pair_id.clone()], encode(7, 1, 6000050000000))
+        .expect_commit_success();
+
+    let pair_details = interface.get_pair_details(vec![pair_config.pair_id.clone()])[0].clone();
+    assert_eq!(pair_details.pool_position.last_price, dec!(60000.5));
+    assert_eq!(pair_details.pool_position.last_update, time_1);
+}
+
 // TODO: keeper rewards
@@ -3,6 +3,7 @@
 use scrypto_test::prelude::*;
 use super::Resources;
 use ::common::*;
+use ::oracle::OracleKey;
 
 use std::path::Path;
 
@@ -85,6 +86,7 @@ pub fn create_components(
         "BASE_AUTHORITY_RESOURCE".to_owned() => resources.base_authority_resource.to_string(encoder),
         "BASE_RESOURCE".to_owned() => resources.base_resource.to_string(encoder),
         "LP_RESOURCE".to_owned() => resources.lp_resource.to_string(encoder),
+        "JUNIOR_LP_RESOURCE".to_owned() => resources.junior_lp_resource.to_string(encoder),
         "REFERRAL_RESOURCE".to_owned() => resources.referral_resource.to_string(encoder),
         "RECOVERY_KEY_RESOURCE".to_owned() => resources.recovery_key_resource.to_string(encoder),
         "PROTOCOL_RESOURCE".to_owned() => resources.protocol_resource.to_string(encoder),
@@ -179,7 +181,7 @@ fn create_oracle(
         oracle_package, 
         "Oracle", 
         "new", 
-        manifest_args!(resources.owner_role.clone(), hashmap!(0 as ListIndex => oracle_key))
+        manifest_args!(resources.owner_role.clone(), hashmap!(0 as ListIndex => OracleKey::Bls12381(oracle_key)))
     ).expect_commit_success().new_component_addresses()[0];
     
     envs.insert("ORACLE_PACKAGE".to_owned(), oracle_package.to_string(encoder));
@@ -26,6 +26,17 @@ pub fn default_pair_config(pair_id: PairId) -> PairConfig {
         funding_share: dec!(0.02),
         fee_0: dec!(0.0005),
         fee_1: dec!(0.0000000005),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     }
 }
 
@@ -48,6 +59,17 @@ pub fn pair_config_zero_fees_and_funding(pair_id: PairId) -> PairConfig {
         funding_share: dec!(0),
         fee_0: dec!(0),
         fee_1: dec!(0),
+        oi_side_ratio_max: dec!(0),
+        oi_combined_ratio_max: dec!(0),
+        oi_value_floor: dec!(0),
+        oi_value_ceiling: dec!(0),
+        margin_tiers: vec![],
+        fee_model: FeeModel::LinearSkew,
+        skew_rebate_rate: dec!(0),
+        funding_model: FundingModel::Skew,
+        price_tolerance: dec!(0.005),
+        mark_ema_seconds: 0,
+        status: PairStatus::Active,
     }
 }
 
@@ -338,6 +360,54 @@ impl ExchangeInterface {
     pub fn update_exchange_config(
         &mut self,
         exchange_config: ExchangeConfig,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.schedule_exchange_config(exchange_config);
+        self.apply_scheduled_config_update(receipt)
+    }
+
+    pub fn update_pair_configs(
+        &mut self, 
+        pair_configs: Vec<PairConfig>
+    ) -> TransactionReceiptV1 {
+        let receipt = self.schedule_pair_configs(pair_configs);
+        self.apply_scheduled_config_update(receipt)
+    }
+
+    pub fn update_collateral_configs(
+        &mut self,
+        collateral_configs: Vec<(ResourceAddress, CollateralConfig)>
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.exchange_component, 
+            "update_collateral_configs", 
+            manifest_args!(collateral_configs)
+        );
+        self.apply_scheduled_config_update(receipt)
+    }
+
+    pub fn remove_collateral_configs(
+        &mut self,
+        collateral_configs: Vec<CollateralConfig>
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.exchange_component, 
+            "remove_collateral_configs", 
+            manifest_args!(collateral_configs)
+        );
+        self.apply_scheduled_config_update(receipt)
+    }
+
+    pub fn remove_pair_config(
+        &mut self,
+        pair_id: PairId,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.schedule_remove_pair_config(pair_id);
+        self.apply_scheduled_config_update(receipt)
+    }
+
+    pub fn schedule_exchange_config(
+        &mut self,
+        exchange_config: ExchangeConfig,
     ) -> TransactionReceiptV1 {
         let receipt = self.ledger.call_method(
             self.components.exchange_component, 
@@ -347,7 +417,7 @@ impl ExchangeInterface {
         receipt
     }
 
-    pub fn update_pair_configs(
+    pub fn schedule_pair_configs(
         &mut self, 
         pair_configs: Vec<PairConfig>
     ) -> TransactionReceiptV1 {
@@ -359,26 +429,78 @@ impl ExchangeInterface {
         receipt
     }
 
-    pub fn update_collateral_configs(
+    pub fn schedule_remove_pair_config(
         &mut self,
-        collateral_configs: Vec<(ResourceAddress, CollateralConfig)>
+        pair_id: PairId,
     ) -> TransactionReceiptV1 {
         let receipt = self.ledger.call_method(
             self.components.exchange_component, 
-            "update_collateral_configs", 
-            manifest_args!(collateral_configs)
+            "remove_pair_config", 
+            manifest_args!(pair_id)
         );
         receipt
     }
 
-    pub fn remove_collateral_configs(
+    /// Applies the config update scheduled by the receipt at its activation time, then restores the ledger time so
+    /// tests are not affected by the config delays, returns the receipt unchanged if nothing was scheduled or the
+    /// update was already applied
+    pub fn apply_scheduled_config_update(
         &mut self,
-        collateral_configs: Vec<CollateralConfig>
+        receipt: TransactionReceiptV1,
+    ) -> TransactionReceiptV1 {
+        if !receipt.is_commit_success() {
+            return receipt;
+        }
+
+        let activated = receipt.expect_commit_success().application_events
+            .iter()
+            .any(|(event_type_identifier, _)| self.ledger.is_event_name_equal::<EventConfigUpdateActivated>(event_type_identifier));
+        if activated {
+            return receipt;
+        }
+
+        let scheduled = receipt.expect_commit_success().application_events
+            .iter()
+            .find_map(|(event_type_identifier, event_data)| {
+                if self.ledger.is_event_name_equal::<EventConfigUpdateScheduled>(event_type_identifier) {
+                    Some(scrypto_decode::<EventConfigUpdateScheduled>(event_data).unwrap())
+                } else {
+                    None
+                }
+            });
+
+        match scheduled {
+            Some(event) => {
+                let current_time = self.ledger_time();
+                set_time(event.activation, &mut self.ledger);
+                let receipt = self.apply_config_updates(vec![event.id]);
+                set_time(current_time, &mut self.ledger);
+                receipt
+            },
+            None => receipt,
+        }
+    }
+
+    pub fn cancel_config_update(
+        &mut self,
+        id: u64,
     ) -> TransactionReceiptV1 {
         let receipt = self.ledger.call_method(
             self.components.exchange_component, 
-            "remove_collateral_configs", 
-            manifest_args!(collateral_configs)
+            "cancel_config_update", 
+            manifest_args!(id)
+        );
+        receipt
+    }
+
+    pub fn apply_config_updates(
+        &mut self,
+        ids: Vec<u64>,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.exchange_component, 
+            "apply_config_updates", 
+            manifest_args!(ids)
         );
         receipt
     }
@@ -586,6 +708,33 @@ impl ExchangeInterface {
         receipt.expect_commit_success().output(1)
     }
 
+    pub fn get_pair_funding_preview(
+        &mut self,
+        pair_ids: Vec<PairId>,
+        prices: HashMap<PairId, Decimal>,
+    ) -> Vec<PairFundingPreview> {
+        let receipt = self.ledger.call_method(
+            self.components.exchange_component, 
+            "get_pair_funding_preview", 
+            manifest_args!(pair_ids, prices)
+        );
+        receipt.expect_commit_success().output(1)
+    }
+
+    pub fn get_pair_history(
+        &mut self,
+        pair_id: PairId,
+        start: Instant,
+        end: Instant,
+    ) -> Vec<PairCheckpoint> {
+        let receipt = self.ledger.call_method(
+            self.components.pool_component, 
+            "get_pair_history", 
+            manifest_args!(pair_id, start, end)
+        );
+        receipt.expect_commit_success().output(1)
+    }
+
     pub fn get_pool_position(
         &mut self,
         pair_id: PairId,
@@ -642,6 +791,17 @@ impl ExchangeInterface {
         receipt.expect_commit_success().output(1)
     }
 
+    pub fn get_pending_config_updates(
+        &mut self,
+    ) -> Vec<(u64, PendingConfigUpdate)> {
+        let receipt = self.ledger.call_method(
+            self.components.exchange_component, 
+            "get_pending_config_updates", 
+            manifest_args!()
+        );
+        receipt.expect_commit_success().output(1)
+    }
+
     pub fn get_collateral_configs(
         &mut self,
     ) -> HashMap<ResourceAddress, CollateralConfig> {
@@ -728,6 +888,262 @@ impl ExchangeInterface {
         receipt
     }
 
+    pub fn add_junior_liquidity(
+        &mut self,
+        token: (ResourceAddress, Decimal)
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.test_account, token.0, token.1)
+            .take_all_from_worktop(token.0, "token")
+            .with_bucket("token", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "add_junior_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn remove_junior_liquidity(
+        &mut self,
+        token: (ResourceAddress, Decimal)
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.test_account, token.0, token.1)
+            .take_all_from_worktop(token.0, "token")
+            .with_bucket("token", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "remove_junior_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn queue_add_liquidity(
+        &mut self,
+        token: (ResourceAddress, Decimal)
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.test_account, token.0, token.1)
+            .take_all_from_worktop(token.0, "token")
+            .with_bucket("token", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "queue_add_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn cancel_add_liquidity(
+        &mut self,
+        receipt: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.test_account, receipt.0, vec![receipt.1])
+            .take_all_from_worktop(receipt.0, "receipt")
+            .with_bucket("receipt", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "cancel_add_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn claim_add_liquidity(
+        &mut self,
+        receipt: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.test_account, receipt.0, vec![receipt.1])
+            .take_all_from_worktop(receipt.0, "receipt")
+            .with_bucket("receipt", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "claim_add_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn queue_remove_liquidity(
+        &mut self,
+        token: (ResourceAddress, Decimal)
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.test_account, token.0, token.1)
+            .take_all_from_worktop(token.0, "token")
+            .with_bucket("token", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "queue_remove_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn cancel_remove_liquidity(
+        &mut self,
+        receipt: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.test_account, receipt.0, vec![receipt.1])
+            .take_all_from_worktop(receipt.0, "receipt")
+            .with_bucket("receipt", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "cancel_remove_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn claim_remove_liquidity(
+        &mut self,
+        receipt: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.test_account, receipt.0, vec![receipt.1])
+            .take_all_from_worktop(receipt.0, "receipt")
+            .with_bucket("receipt", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "claim_remove_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn queue_remove_locked_liquidity(
+        &mut self,
+        receipt: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.test_account, receipt.0, vec![receipt.1])
+            .take_all_from_worktop(receipt.0, "receipt")
+            .with_bucket("receipt", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "queue_remove_locked_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn lock_liquidity(
+        &mut self,
+        token: (ResourceAddress, Decimal),
+        term: i64,
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_from_account(self.test_account, token.0, token.1)
+            .take_all_from_worktop(token.0, "token")
+            .with_bucket("token", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "lock_liquidity", 
+                    manifest_args!(bucket, term)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn claim_liquidity_lock_rewards(
+        &mut self,
+        lock_proof: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .create_proof_from_account_of_non_fungible(
+                self.test_account, 
+                NonFungibleGlobalId::new(lock_proof.0, lock_proof.1)
+            )
+            .pop_from_auth_zone("lock")
+            .with_name_lookup(|manifest, lookup| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "claim_liquidity_lock_rewards", 
+                    manifest_args!(lookup.proof("lock"))
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn unlock_liquidity(
+        &mut self,
+        receipt: (ResourceAddress, NonFungibleLocalId),
+    ) -> TransactionReceiptV1 {
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .withdraw_non_fungibles_from_account(self.test_account, receipt.0, vec![receipt.1])
+            .take_all_from_worktop(receipt.0, "receipt")
+            .with_bucket("receipt", |manifest, bucket| {
+                manifest.call_method(
+                    self.components.exchange_component, 
+                    "unlock_liquidity", 
+                    manifest_args!(bucket)
+                )
+            })
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
     pub fn create_referral_codes(
         &mut self,
         referral_proof: (ResourceAddress, NonFungibleLocalId),
@@ -1206,7 +1622,7 @@ impl ExchangeInterface {
             let price_data = scrypto_encode(&prices).unwrap();
             let price_data_hash = keccak256_hash(&price_data).to_vec();
             let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
-            Some((price_data, price_signature, 0 as ListIndex))
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
         } else {
             None
         };
@@ -1228,6 +1644,34 @@ impl ExchangeInterface {
         receipt
     }
 
+    pub fn process_request_versioned(
+        &mut self,
+        margin_account_component: ComponentAddress,
+        index: ListIndex,
+        prices: VersionedPrices,
+    ) -> TransactionReceiptV1 {
+        let price_data = scrypto_encode(&prices).unwrap();
+        let price_data_hash = keccak256_hash(&price_data).to_vec();
+        let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
+        let price_updates = Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]));
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.components.exchange_component, 
+                "process_request", 
+                manifest_args!(
+                    margin_account_component, 
+                    index, 
+                    price_updates
+                )
+            )
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
     pub fn swap_debt(
         &mut self,
         margin_account_component: ComponentAddress,
@@ -1239,7 +1683,7 @@ impl ExchangeInterface {
             let price_data = scrypto_encode(&prices).unwrap();
             let price_data_hash = keccak256_hash(&price_data).to_vec();
             let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
-            Some((price_data, price_signature, 0 as ListIndex))
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
         } else {
             None
         };
@@ -1271,7 +1715,7 @@ impl ExchangeInterface {
             let price_data = scrypto_encode(&prices).unwrap();
             let price_data_hash = keccak256_hash(&price_data).to_vec();
             let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
-            Some((price_data, price_signature, 0 as ListIndex))
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
         } else {
             None
         };
@@ -1303,7 +1747,7 @@ impl ExchangeInterface {
             let price_data = scrypto_encode(&prices).unwrap();
             let price_data_hash = keccak256_hash(&price_data).to_vec();
             let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
-            Some((price_data, price_signature, 0 as ListIndex))
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
         } else {
             None
         };
@@ -1331,7 +1775,7 @@ impl ExchangeInterface {
             let price_data = scrypto_encode(&prices).unwrap();
             let price_data_hash = keccak256_hash(&price_data).to_vec();
             let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
-            Some((price_data, price_signature, 0 as ListIndex))
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
         } else {
             None
         };
@@ -1349,6 +1793,34 @@ impl ExchangeInterface {
         receipt
     }
 
+    pub fn settle_position(
+        &mut self,
+        margin_account_component: ComponentAddress,
+        pair_id: PairId,
+        prices: Option<Vec<Price>>,
+    ) -> TransactionReceiptV1 {
+        let price_updates = if let Some(prices) = prices {
+            let price_data = scrypto_encode(&prices).unwrap();
+            let price_data_hash = keccak256_hash(&price_data).to_vec();
+            let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
+        } else {
+            None
+        };
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.components.exchange_component, 
+                "settle_position", 
+                manifest_args!(margin_account_component, pair_id, price_updates)
+            )
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
     pub fn update_pairs(
         &mut self,
         pair_ids: Vec<PairId>,
@@ -1358,7 +1830,7 @@ impl ExchangeInterface {
             let price_data = scrypto_encode(&prices).unwrap();
             let price_data_hash = keccak256_hash(&price_data).to_vec();
             let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
-            Some((price_data, price_signature, 0 as ListIndex))
+            Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]))
         } else {
             None
         };
@@ -1376,6 +1848,226 @@ impl ExchangeInterface {
         receipt
     }
 
+    pub fn update_pairs_with_data(
+        &mut self,
+        pair_ids: Vec<PairId>,
+        price_data: Vec<u8>,
+    ) -> TransactionReceiptV1 {
+        let price_data_hash = keccak256_hash(&price_data).to_vec();
+        let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
+        let price_updates = Some((price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]));
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.components.exchange_component, 
+                "update_pairs", 
+                manifest_args!(pair_ids, price_updates)
+            )
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn update_pairs_multisig(
+        &mut self,
+        pair_ids: Vec<PairId>,
+        prices: Vec<Price>,
+        signers: Vec<(ListIndex, u64)>,
+    ) -> TransactionReceiptV1 {
+        let price_data = scrypto_encode(&prices).unwrap();
+        let price_data_hash = keccak256_hash(&price_data).to_vec();
+        let price_signatures: Vec<Bls12381G2Signature> = signers.iter()
+            .map(|(_, key_seed)| Bls12381G1PrivateKey::from_u64(*key_seed).unwrap().sign_v1(&price_data_hash))
+            .collect();
+        let price_signature = Bls12381G2Signature::aggregate(&price_signatures, true).unwrap();
+        let key_ids: Vec<ListIndex> = signers.iter().map(|(key_id, _)| *key_id).collect();
+        let price_updates = Some((price_data, OracleSignature::Bls12381(price_signature), key_ids));
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.components.exchange_component, 
+                "update_pairs", 
+                manifest_args!(pair_ids, price_updates)
+            )
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn update_pairs_with_signature(
+        &mut self,
+        pair_ids: Vec<PairId>,
+        price_data: Vec<u8>,
+        price_signature: OracleSignature,
+        key_ids: Vec<ListIndex>,
+    ) -> TransactionReceiptV1 {
+        let price_updates = Some((price_data, price_signature, key_ids));
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.components.exchange_component, 
+                "update_pairs", 
+                manifest_args!(pair_ids, price_updates)
+            )
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
+    pub fn oracle_add_key(
+        &mut self,
+        key_id: ListIndex,
+        key_seed: u64,
+    ) -> TransactionReceiptV1 {
+        let public_key = Bls12381G1PrivateKey::from_u64(key_seed).unwrap().public_key();
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "add_key", 
+            manifest_args!(key_id, OracleKey::Bls12381(public_key))
+        );
+        receipt
+    }
+
+    pub fn oracle_add_oracle_key(
+        &mut self,
+        key_id: ListIndex,
+        key: OracleKey,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "add_key", 
+            manifest_args!(key_id, key)
+        );
+        receipt
+    }
+
+    pub fn oracle_update_key_validity(
+        &mut self,
+        key_id: ListIndex,
+        valid_from: Option<Instant>,
+        valid_until: Option<Instant>,
+        pairs: Option<HashSet<PairId>>,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "update_key_validity", 
+            manifest_args!(key_id, valid_from, valid_until, pairs)
+        );
+        receipt
+    }
+
+    pub fn oracle_remove_key(
+        &mut self,
+        key_id: ListIndex,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "remove_key", 
+            manifest_args!(key_id)
+        );
+        receipt
+    }
+
+    pub fn oracle_update_threshold(
+        &mut self,
+        threshold: u64,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "update_threshold", 
+            manifest_args!(threshold)
+        );
+        receipt
+    }
+
+    pub fn oracle_update_deviation_rate(
+        &mut self,
+        pair_id: PairId,
+        deviation_rate: Option<Decimal>,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "update_deviation_rate", 
+            manifest_args!(pair_id, deviation_rate)
+        );
+        receipt
+    }
+
+    pub fn oracle_update_pair_index(
+        &mut self,
+        index: u16,
+        pair_id: Option<PairId>,
+    ) -> TransactionReceiptV1 {
+        let receipt = self.ledger.call_method(
+            self.components.oracle_component, 
+            "update_pair_index", 
+            manifest_args!(index, pair_id)
+        );
+        receipt
+    }
+
+    pub fn create_oracle_component(
+        &mut self,
+        key_seed: u64,
+    ) -> ComponentAddress {
+        let public_key = Bls12381G1PrivateKey::from_u64(key_seed).unwrap().public_key();
+        let receipt = self.ledger.call_function(
+            self.components.oracle_package,
+            "Oracle",
+            "new",
+            manifest_args!(self.resources.owner_role.clone(), hashmap!(0 as ListIndex => OracleKey::Bls12381(public_key)))
+        );
+        receipt.expect_commit_success().new_component_addresses()[0]
+    }
+
+    pub fn oracle_push_prices(
+        &mut self,
+        oracle_component: ComponentAddress,
+        prices: Vec<Price>,
+        key_seed: u64,
+    ) -> TransactionReceiptV1 {
+        let pair_ids: HashSet<PairId> = prices.iter().map(|price| price.pair.clone()).collect();
+        let price_data = scrypto_encode(&prices).unwrap();
+        let price_data_hash = keccak256_hash(&price_data).to_vec();
+        let price_signature = Bls12381G1PrivateKey::from_u64(key_seed).unwrap().sign_v1(&price_data_hash);
+
+        let receipt = self.ledger.call_method(
+            oracle_component,
+            "push_and_get_prices",
+            manifest_args!(pair_ids, price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex])
+        );
+        receipt
+    }
+
+    pub fn process_liquidity_requests(
+        &mut self,
+        n: ListIndex,
+        prices: Vec<Price>,
+    ) -> TransactionReceiptV1 {
+        let price_data = scrypto_encode(&prices).unwrap();
+        let price_data_hash = keccak256_hash(&price_data).to_vec();
+        let price_signature = Bls12381G1PrivateKey::from_u64(self.components.oracle_key_seed).unwrap().sign_v1(&price_data_hash);
+        let price_updates = (price_data, OracleSignature::Bls12381(price_signature), vec![0 as ListIndex]);
+
+        let manifest = ManifestBuilder::new()
+            .lock_fee_from_faucet()
+            .call_method(
+                self.components.exchange_component, 
+                "process_liquidity_requests", 
+                manifest_args!(n, price_updates)
+            )
+            .deposit_batch(self.test_account)
+            .build();
+        let receipt = self.ledger.execute_manifest(manifest, vec![NonFungibleGlobalId::from_public_key(&self.public_key)]);
+        receipt
+    }
+
     pub fn swap_protocol_fee(
         &mut self,
         token: (ResourceAddress, Decimal),
@@ -13,6 +13,7 @@ pub struct Resources {
     pub base_authority_resource: ResourceAddress,
     pub base_resource: ResourceAddress,
     pub lp_resource: ResourceAddress,
+    pub junior_lp_resource: ResourceAddress,
     pub protocol_resource: ResourceAddress,
     pub referral_resource: ResourceAddress,
     pub recovery_key_resource: ResourceAddress,
@@ -30,6 +31,7 @@ pub fn create_resources(
 
     let base_resource = create_base_resource(account, owner_role.clone(), base_authority_resource, ledger);
     let lp_resource = create_lp_resource(owner_role.clone(), authority_resource, ledger);
+    let junior_lp_resource = create_junior_lp_resource(owner_role.clone(), authority_resource, ledger);
     let protocol_resource = mint_protocol_resource(account, owner_role.clone(), ledger);
     let referral_resource = create_referral_resource(owner_role.clone(), authority_resource, ledger);
     let recovery_key_resource = create_recovery_key_resource(owner_role.clone(), authority_resource, ledger);
@@ -42,6 +44,7 @@ pub fn create_resources(
         base_authority_resource,
         base_resource,
         lp_resource,
+        junior_lp_resource,
         protocol_resource,
         referral_resource,
         recovery_key_resource,
@@ -171,6 +174,46 @@ fn create_lp_resource(
     receipt.expect_commit_success().new_resource_addresses()[0]
 }
 
+fn create_junior_lp_resource(
+    owner_role: OwnerRole, 
+    authority_resource: ResourceAddress, 
+    ledger: &mut LedgerSimulator<NoExtension, InMemorySubstateDatabase>
+) -> ResourceAddress {
+    let metadata = metadata!(
+        init {
+            "name" => "Surge Junior LP", updatable;
+            "symbol" => "SJLP", updatable;
+            "description" => "Surge liquidity pool junior tranche LP token.", updatable;
+            "icon_url" => Url::of("https://surge.trade/images/surge_lp_token.png"), updatable;
+            "info_url" => Url::of("https://surge.trade"), updatable;
+        }
+    );
+    let resource_roles = FungibleResourceRoles {
+        mint_roles: mint_roles! {
+            minter => rule!(require(authority_resource));
+            minter_updater => rule!(deny_all);
+        },
+        burn_roles: burn_roles! {
+            burner => rule!(require(authority_resource));
+            burner_updater => rule!(deny_all);
+        },
+        ..Default::default()
+    };
+    let manifest = ManifestBuilder::new()
+        .lock_fee_from_faucet()
+        .create_fungible_resource(
+            owner_role, 
+            true,
+            DIVISIBILITY_MAXIMUM,
+            resource_roles, 
+            metadata, 
+            None
+        )
+        .build();
+    let receipt = ledger.execute_manifest(manifest, vec![]);
+    receipt.expect_commit_success().new_resource_addresses()[0]
+}
+
 fn create_referral_resource(
     owner_role: OwnerRole, 
     authority_resource: ResourceAddress, 
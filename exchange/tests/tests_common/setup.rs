@@ -24,6 +24,7 @@ pub fn setup_dapp_definition(
         .set_metadata(account, "icon", MetadataValue::Url(UncheckedUrl::of("https://surge.trade/images/icon_dapp.png")))
         .set_metadata(resources.base_resource, "dapp_definitions", MetadataValue::GlobalAddressArray(vec![dapp_account.into()]))
         .set_metadata(resources.lp_resource, "dapp_definitions", MetadataValue::GlobalAddressArray(vec![dapp_account.into()]))
+        .set_metadata(resources.junior_lp_resource, "dapp_definitions", MetadataValue::GlobalAddressArray(vec![dapp_account.into()]))
         .set_metadata(resources.protocol_resource, "dapp_definitions", MetadataValue::GlobalAddressArray(vec![dapp_account.into()]))
         .set_metadata(resources.referral_resource, "dapp_definitions", MetadataValue::GlobalAddressArray(vec![dapp_account.into()]))
         .set_metadata(resources.keeper_reward_resource, "dapp_definitions", MetadataValue::GlobalAddressArray(vec![dapp_account.into()]))
@@ -16,6 +16,17 @@ def encode_prices(prices) -> bytes:
     return sbor_bytes
 
 
+def encode_prices_compact(prices, pair_indices) -> bytes:
+    base_timestamp = min(int(price["timestamp"]) for price in prices)
+    data = bytes([0x01]) + base_timestamp.to_bytes(8, 'big', signed=True)
+    for price in prices:
+        data += pair_indices[price["pair"]].to_bytes(2, 'big')
+        data += (int(price["timestamp"]) - base_timestamp).to_bytes(2, 'big')
+        data += round(price["quote"] * 10**8).to_bytes(8, 'big')
+
+    return data
+
+
 # EXAMPLE
 prices= [
    {
@@ -1,12 +1,30 @@
 pub mod errors;
+pub mod events;
+pub mod signatures;
 pub mod structs;
 
 use scrypto::prelude::*;
 use common::{PairId, ListIndex, _AUTHORITY_RESOURCE};
 pub use self::errors::*;
+pub use self::events::*;
+pub use self::signatures::*;
 pub use self::structs::*;
 
 #[blueprint]
+#[types(
+    PairId,
+    OraclePrice,
+    QuarantinedPrice,
+    PriceHistoryEntry,
+    (PairId, u64),
+    Decimal,
+    u16,
+    u64,
+)]
+#[events(
+    EventPriceQuarantined,
+    EventPriceConfirmed,
+)]
 mod oracle_mod {
     const AUTHORITY_RESOURCE: ResourceAddress = _AUTHORITY_RESOURCE;
 
@@ -19,24 +37,45 @@ mod oracle_mod {
         methods {
             add_key => restrict_to: [OWNER];
             remove_key => restrict_to: [OWNER];
+            update_key_validity => restrict_to: [OWNER];
+            update_threshold => restrict_to: [OWNER];
+            update_deviation_rate => restrict_to: [OWNER];
+            update_pair_index => restrict_to: [OWNER];
             remove_price => restrict_to: [OWNER];
             push_and_get_prices_with_auth => restrict_to: [authority];
             get_prices_with_auth => restrict_to: [authority];
+            get_high_low_with_auth => restrict_to: [authority];
             push_and_get_prices => restrict_to: [push_user];
             get_prices => restrict_to: [get_user];
+            get_price_at => restrict_to: [get_user];
+            get_high_low => restrict_to: [get_user];
         }
     );
 
     struct Oracle {
-        keys: HashMap<ListIndex, Bls12381G1PublicKey>,
-        prices: HashMap<PairId, (Decimal, Instant)>,
+        keys: HashMap<ListIndex, OracleKeyEntry>,
+        threshold: u64,
+        prices: KeyValueStore<PairId, OraclePrice>,
+        deviation_rates: KeyValueStore<PairId, Decimal>,
+        quarantined_prices: KeyValueStore<PairId, QuarantinedPrice>,
+        pair_indices: KeyValueStore<u16, PairId>,
+        price_history: KeyValueStore<(PairId, u64), PriceHistoryEntry>,
+        price_history_heads: KeyValueStore<PairId, u64>,
     }
 
     impl Oracle {
-        pub fn new(owner_role: OwnerRole, keys: HashMap<ListIndex, Bls12381G1PublicKey>) -> Global<Oracle> {    
+        pub fn new(owner_role: OwnerRole, keys: HashMap<ListIndex, OracleKey>) -> Global<Oracle> {    
+            let keys = keys.into_iter().map(|(id, key)| (id, OracleKeyEntry::new(key))).collect();
+
             Self {
                 keys,
-                prices: HashMap::new()
+                threshold: 1,
+                prices: KeyValueStore::new_with_registered_type(),
+                deviation_rates: KeyValueStore::new_with_registered_type(),
+                quarantined_prices: KeyValueStore::new_with_registered_type(),
+                pair_indices: KeyValueStore::new_with_registered_type(),
+                price_history: KeyValueStore::new_with_registered_type(),
+                price_history_heads: KeyValueStore::new_with_registered_type(),
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
@@ -57,76 +96,282 @@ mod oracle_mod {
                 init {
                     add_key => Free, locked;
                     remove_key => Free, locked;
+                    update_key_validity => Free, locked;
+                    update_threshold => Free, locked;
+                    update_deviation_rate => Free, locked;
+                    update_pair_index => Free, locked;
                     remove_price => Free, locked;
                     push_and_get_prices_with_auth => Free, locked;
                     get_prices_with_auth => Free, locked;
+                    get_high_low_with_auth => Free, locked;
                     push_and_get_prices => Usd(dec!(0.1)), updatable;
                     get_prices => Usd(dec!(0.1)), updatable;
+                    get_price_at => Usd(dec!(0.1)), updatable;
+                    get_high_low => Usd(dec!(0.1)), updatable;
                 }
             })
             .globalize()
         }
 
-        pub fn add_key(&mut self, id: ListIndex, public_key: Bls12381G1PublicKey) {
+        pub fn add_key(&mut self, id: ListIndex, public_key: OracleKey) {
             assert!(
                 !self.keys.contains_key(&id),
                 "{}, VALUE:{}, REQUIRED:{:?}, OP:!contains |", ERROR_KEY_ID_ALREADY_EXISTS, id, self.keys.keys()
             );
 
-            self.keys.insert(id, public_key);
+            self.keys.insert(id, OracleKeyEntry::new(public_key));
         }
 
         pub fn remove_key(&mut self, id: ListIndex) {
             self.keys.remove(&id);
+            self._assert_threshold_reachable(self.threshold);
+        }
+
+        pub fn update_key_validity(
+            &mut self, 
+            id: ListIndex, 
+            valid_from: Option<Instant>, 
+            valid_until: Option<Instant>, 
+            pairs: Option<HashSet<PairId>>,
+        ) {
+            if let (Some(valid_from), Some(valid_until)) = (valid_from, valid_until) {
+                assert!(
+                    valid_from.compare(valid_until, TimeComparisonOperator::Lt),
+                    "{}, VALUE:{:?}, REQUIRED:{:?}, OP:< |", ERROR_INVALID_KEY_VALIDITY, valid_from, valid_until
+                );
+            }
+
+            let entry = self.keys.get_mut(&id).expect(ERROR_INVALID_KEY_ID);
+            entry.valid_from = valid_from;
+            entry.valid_until = valid_until;
+            entry.pairs = pairs;
+        }
+
+        pub fn update_threshold(&mut self, threshold: u64) {
+            assert!(
+                threshold > 0,
+                "{}, VALUE:{}, REQUIRED:{}, OP:> |", ERROR_INVALID_THRESHOLD, threshold, 0
+            );
+            self._assert_threshold_reachable(threshold);
+
+            self.threshold = threshold;
+        }
+
+        pub fn update_deviation_rate(&mut self, pair_id: PairId, deviation_rate: Option<Decimal>) {
+            if let Some(deviation_rate) = deviation_rate {
+                assert!(
+                    deviation_rate.is_positive(),
+                    "{}, VALUE:{}, REQUIRED:0, OP:> |", ERROR_INVALID_DEVIATION_RATE, deviation_rate
+                );
+
+                self.deviation_rates.insert(pair_id, deviation_rate);
+            } else {
+                self.deviation_rates.remove(&pair_id);
+                self.quarantined_prices.remove(&pair_id);
+            }
+        }
+
+        pub fn update_pair_index(&mut self, index: u16, pair_id: Option<PairId>) {
+            if let Some(pair_id) = pair_id {
+                self.pair_indices.insert(index, pair_id);
+            } else {
+                self.pair_indices.remove(&index);
+            }
         }
 
         pub fn remove_price(&mut self, pair_id: PairId) {
             self.prices.remove(&pair_id);
+            self.quarantined_prices.remove(&pair_id);
+            let head = self.price_history_heads.remove(&pair_id).unwrap_or(0);
+            for slot in 0..head.min(PRICE_HISTORY_LENGTH) {
+                self.price_history.remove(&(pair_id.clone(), slot));
+            }
         }
 
-        pub fn push_and_get_prices_with_auth(&mut self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: Bls12381G2Signature, key_id: ListIndex) -> HashMap<PairId, (Decimal, Instant)> {
-            self._push_and_get_prices(pair_ids, data, signature, key_id)
+        pub fn push_and_get_prices_with_auth(&mut self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: OracleSignature, key_ids: Vec<ListIndex>) -> HashMap<PairId, (Decimal, Instant, Decimal)> {
+            self._push_prices(data, signature, key_ids);
+            self._get_prices_with_confidence(pair_ids)
         }
 
-        pub fn get_prices_with_auth(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant)> {
-            self._get_prices(pair_ids)
+        pub fn get_prices_with_auth(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant, Decimal)> {
+            self._get_prices_with_confidence(pair_ids)
         }
 
-        pub fn push_and_get_prices(&mut self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: Bls12381G2Signature, key_id: ListIndex) -> HashMap<PairId, (Decimal, Instant)> {
-            self._push_and_get_prices(pair_ids, data, signature, key_id)
+        pub fn push_and_get_prices(&mut self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: OracleSignature, key_ids: Vec<ListIndex>) -> HashMap<PairId, (Decimal, Instant)> {
+            self._push_prices(data, signature, key_ids);
+            self._get_prices(pair_ids)
         }
 
         pub fn get_prices(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant)> {
             self._get_prices(pair_ids)
         }
 
-        fn _push_and_get_prices(&mut self, pair_ids: HashSet<PairId>, data: Vec<u8>, signature: Bls12381G2Signature, key_id: ListIndex) -> HashMap<PairId, (Decimal, Instant)> {
-            let prices: Vec<Price> = scrypto_decode(&data).expect(ERROR_INVALID_DATA);
+        pub fn get_high_low_with_auth(&self, pair_id: PairId, from: Instant, to: Instant) -> Option<HighLow> {
+            HighLow::from_entries(&self._price_history(&pair_id), from, to)
+        }
+
+        pub fn get_price_at(&self, pair_id: PairId, timestamp: Instant) -> Option<(Decimal, Instant)> {
+            self._price_history(&pair_id).into_iter()
+                .filter(|entry| entry.timestamp.compare(timestamp, TimeComparisonOperator::Lte))
+                .max_by_key(|entry| entry.timestamp.seconds_since_unix_epoch)
+                .map(|entry| (entry.quote, entry.timestamp))
+        }
+
+        pub fn get_high_low(&self, pair_id: PairId, from: Instant, to: Instant) -> Option<(Decimal, Decimal)> {
+            self._price_history(&pair_id).into_iter()
+                .filter(|entry| entry.timestamp.compare(from, TimeComparisonOperator::Gte) && entry.timestamp.compare(to, TimeComparisonOperator::Lte))
+                .fold(None, |high_low, entry| match high_low {
+                    Some((high, low)) => Some((entry.quote.max(high), entry.quote.min(low))),
+                    None => Some((entry.quote, entry.quote)),
+                })
+        }
+
+        fn _assert_threshold_reachable(&self, threshold: u64) {
+            let current_time = Clock::current_time_rounded_to_seconds();
+            let valid_keys = self.keys.values().filter(|entry| entry.is_valid_at(current_time)).count() as u64;
+            assert!(
+                threshold <= valid_keys,
+                "{}, VALUE:{}, REQUIRED:{}, OP:<= |", ERROR_THRESHOLD_UNREACHABLE, threshold, valid_keys
+            );
+        }
+
+        fn _push_prices(&mut self, data: Vec<u8>, signature: OracleSignature, key_ids: Vec<ListIndex>) {
+            let prices: Vec<PriceV2> = if data.first() == Some(&COMPACT_PRICES_PREFIX) {
+                CompactPrice::decode_all(&data).expect(ERROR_INVALID_DATA).into_iter().map(|compact_price| {
+                    let pair = self.pair_indices.get(&compact_price.index).expect(ERROR_MISSING_PAIR_INDEX).clone();
+                    compact_price.into_price(pair)
+                }).collect()
+            } else {
+                match scrypto_decode::<VersionedPrices>(&data) {
+                    Ok(versioned_prices) => versioned_prices.into_latest(),
+                    Err(_) => scrypto_decode::<Vec<Price>>(&data).expect(ERROR_INVALID_DATA).into_iter().map(PriceV2::from).collect(),
+                }
+            };
+
+            let signers: HashSet<ListIndex> = key_ids.iter().cloned().collect();
+            assert!(
+                signers.len() == key_ids.len(),
+                "{}, VALUE:{:?}, REQUIRED:unique, OP:unique |", ERROR_DUPLICATE_KEY_ID, key_ids
+            );
+            assert!(
+                key_ids.len() as u64 >= self.threshold,
+                "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_INSUFFICIENT_SIGNERS, key_ids.len(), self.threshold
+            );
+
+            let current_time = Clock::current_time_rounded_to_seconds();
+            let entries: Vec<&OracleKeyEntry> = key_ids.iter().map(|key_id| self.keys.get(key_id).expect(ERROR_INVALID_KEY_ID)).collect();
+            for (key_id, entry) in key_ids.iter().zip(entries.iter()) {
+                assert!(
+                    entry.is_valid_at(current_time),
+                    "{}, VALUE:{:?}, REQUIRED:{:?}..{:?}, OP:contains |", ERROR_KEY_NOT_VALID, current_time, entry.valid_from, entry.valid_until
+                );
+                for price in prices.iter() {
+                    assert!(
+                        entry.is_allowed_pair(&price.pair),
+                        "{}, VALUE:{}, REQUIRED:{}, OP:allowed |", ERROR_KEY_PAIR_NOT_ALLOWED, price.pair, key_id
+                    );
+                }
+            }
 
             let hash = CryptoUtils::keccak256_hash(data).to_vec();
-            let key = *self.keys.get(&key_id).expect(ERROR_INVALID_KEY_ID);
+            let keys: Vec<OracleKey> = entries.into_iter().map(|entry| entry.key.clone()).collect();
             assert!(
-                CryptoUtils::bls12381_v1_verify(hash.clone(), key, signature),
-                "{}, VALUE:{}, REQUIRED:{}, OP:verify |", ERROR_INVALID_SIGNATURE, signature, key
+                signature.verify(hash, keys).expect(ERROR_SIGNATURE_SCHEME_MISMATCH),
+                "{}, VALUE:{:?}, REQUIRED:{:?}, OP:verify |", ERROR_INVALID_SIGNATURE, signature, key_ids
             );
 
             prices.into_iter().for_each(|p1| {
-                self.prices.entry(p1.pair)
-                    .and_modify(|p| {
-                        if p1.timestamp.compare(p.1, TimeComparisonOperator::Gt) {
-                            *p = (p1.quote, p1.timestamp);
+                assert!(
+                    !p1.confidence.is_negative() && p1.confidence < p1.quote,
+                    "{}, VALUE:{}, REQUIRED:{}, OP:< |", ERROR_INVALID_CONFIDENCE, p1.confidence, p1.quote
+                );
+
+                let last_price = self.prices.get(&p1.pair).map(|price| price.clone());
+                if let Some(OraclePrice { quote: last_quote, timestamp: last_timestamp, .. }) = last_price {
+                    if !p1.timestamp.compare(last_timestamp, TimeComparisonOperator::Gt) {
+                        return;
+                    }
+
+                    let deviation_rate = self.deviation_rates.get(&p1.pair).map(|deviation_rate| *deviation_rate);
+                    if let Some(deviation_rate) = deviation_rate {
+                        if !self._within_deviation(last_quote, last_timestamp, p1.quote, p1.timestamp, deviation_rate) {
+                            let confirmed = self.quarantined_prices.get(&p1.pair).is_some_and(|quarantined| {
+                                key_ids.iter().any(|key_id| !quarantined.key_ids.contains(key_id)) &&
+                                !p1.timestamp.compare(quarantined.timestamp, TimeComparisonOperator::Lt) &&
+                                self._within_deviation(quarantined.quote, quarantined.timestamp, p1.quote, p1.timestamp, deviation_rate)
+                            });
+
+                            if !confirmed {
+                                self.quarantined_prices.insert(p1.pair.clone(), QuarantinedPrice {
+                                    quote: p1.quote,
+                                    timestamp: p1.timestamp,
+                                    key_ids: key_ids.clone(),
+                                });
+                                Runtime::emit_event(EventPriceQuarantined {
+                                    pair_id: p1.pair,
+                                    quote: p1.quote,
+                                    timestamp: p1.timestamp,
+                                    last_quote,
+                                    last_timestamp,
+                                    key_ids: key_ids.clone(),
+                                });
+                                return;
+                            }
+
+                            Runtime::emit_event(EventPriceConfirmed {
+                                pair_id: p1.pair.clone(),
+                                quote: p1.quote,
+                                timestamp: p1.timestamp,
+                                key_ids: key_ids.clone(),
+                            });
                         }
-                    })
-                    .or_insert((p1.quote, p1.timestamp));
+                    }
+                }
+
+                self.quarantined_prices.remove(&p1.pair);
+                self._push_price_history(&p1.pair, PriceHistoryEntry {
+                    quote: p1.quote,
+                    confidence: p1.confidence,
+                    timestamp: p1.timestamp,
+                });
+                self.prices.insert(p1.pair, OraclePrice {
+                    quote: p1.quote,
+                    timestamp: p1.timestamp,
+                    confidence: p1.confidence,
+                });
             });
+        }
 
-            self._get_prices(pair_ids)
+        fn _within_deviation(&self, last_quote: Decimal, last_timestamp: Instant, quote: Decimal, timestamp: Instant, deviation_rate: Decimal) -> bool {
+            let seconds = timestamp.seconds_since_unix_epoch - last_timestamp.seconds_since_unix_epoch;
+            let deviation_max = last_quote * deviation_rate * Decimal::from(seconds);
+            quote >= last_quote - deviation_max && quote <= last_quote + deviation_max
         }
 
         fn _get_prices(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant)> {
             pair_ids.into_iter().map(|pair_id| {
-                let (quote, timestamp) = *self.prices.get(&pair_id).expect(ERROR_MISSING_PAIR);
-                (pair_id, (quote, timestamp))
+                let price = self.prices.get(&pair_id).expect(ERROR_MISSING_PAIR).clone();
+                (pair_id, (price.quote, price.timestamp))
+            }).collect()
+        }
+
+        fn _push_price_history(&mut self, pair_id: &PairId, entry: PriceHistoryEntry) {
+            let head = self.price_history_heads.get(pair_id).map_or(0, |head| *head);
+            self.price_history.insert((pair_id.clone(), head % PRICE_HISTORY_LENGTH), entry);
+            self.price_history_heads.insert(pair_id.clone(), head + 1);
+        }
+
+        fn _price_history(&self, pair_id: &PairId) -> Vec<PriceHistoryEntry> {
+            let head = self.price_history_heads.get(pair_id).map_or(0, |head| *head);
+            (0..head.min(PRICE_HISTORY_LENGTH))
+                .filter_map(|slot| self.price_history.get(&(pair_id.clone(), slot)).map(|entry| entry.clone()))
+                .collect()
+        }
+
+        fn _get_prices_with_confidence(&self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant, Decimal)> {
+            pair_ids.into_iter().map(|pair_id| {
+                let price = self.prices.get(&pair_id).expect(ERROR_MISSING_PAIR).clone();
+                (pair_id, (price.quote, price.timestamp, price.confidence))
             }).collect()
         }
     }
@@ -4,3 +4,14 @@ pub const ERROR_INVALID_DATA: &str = "Invalid data";
 pub const ERROR_INVALID_KEY_ID: &str = "Invalid key id";
 pub const ERROR_INVALID_SIGNATURE: &str = "Invalid signature";
 pub const ERROR_MISSING_PAIR: &str = "Pair not found";
+pub const ERROR_INVALID_THRESHOLD: &str = "Invalid threshold";
+pub const ERROR_THRESHOLD_UNREACHABLE: &str = "Threshold exceeds valid keys";
+pub const ERROR_DUPLICATE_KEY_ID: &str = "Duplicate key id";
+pub const ERROR_INSUFFICIENT_SIGNERS: &str = "Insufficient signers";
+pub const ERROR_INVALID_DEVIATION_RATE: &str = "Invalid deviation rate";
+pub const ERROR_INVALID_CONFIDENCE: &str = "Invalid price confidence";
+pub const ERROR_MISSING_PAIR_INDEX: &str = "Pair index not found";
+pub const ERROR_SIGNATURE_SCHEME_MISMATCH: &str = "Signature scheme does not match key";
+pub const ERROR_INVALID_KEY_VALIDITY: &str = "Invalid key validity";
+pub const ERROR_KEY_NOT_VALID: &str = "Key not valid";
+pub const ERROR_KEY_PAIR_NOT_ALLOWED: &str = "Key not allowed for pair";
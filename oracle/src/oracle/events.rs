@@ -0,0 +1,20 @@
+use scrypto::prelude::*;
+use common::{PairId, ListIndex};
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventPriceQuarantined {
+    pub pair_id: PairId,
+    pub quote: Decimal,
+    pub timestamp: Instant,
+    pub last_quote: Decimal,
+    pub last_timestamp: Instant,
+    pub key_ids: Vec<ListIndex>,
+}
+
+#[derive(ScryptoSbor, ScryptoEvent, Clone, Debug)]
+pub struct EventPriceConfirmed {
+    pub pair_id: PairId,
+    pub quote: Decimal,
+    pub timestamp: Instant,
+    pub key_ids: Vec<ListIndex>,
+}
@@ -0,0 +1,70 @@
+use scrypto::prelude::*;
+use ed25519_dalek::{Signature as DalekSignature, VerifyingKey as DalekVerifyingKey};
+use k256::ecdsa::{Signature as K256Signature, VerifyingKey as K256VerifyingKey};
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug)]
+pub enum OracleKey {
+    Bls12381(Bls12381G1PublicKey),
+    Ed25519(Ed25519PublicKey),
+    Secp256k1(Secp256k1PublicKey),
+}
+
+#[derive(ScryptoSbor, ManifestSbor, Clone, Debug)]
+pub enum OracleSignature {
+    /// Aggregated signature of all signing keys
+    Bls12381(Bls12381G2Signature),
+    /// Signatures of the signing keys, in the same order as the key ids
+    Ed25519(Vec<Ed25519Signature>),
+    /// Signatures of the signing keys, in the same order as the key ids
+    Secp256k1(Vec<Secp256k1Signature>),
+}
+
+impl OracleSignature {
+    /// Verifies the signature of a message hash, returns None if a key does not match the signature scheme
+    pub fn verify(&self, hash: Vec<u8>, keys: Vec<OracleKey>) -> Option<bool> {
+        match self {
+            OracleSignature::Bls12381(signature) => {
+                let keys = keys.into_iter().map(|key| match key {
+                    OracleKey::Bls12381(key) => Some(key),
+                    _ => None,
+                }).collect::<Option<Vec<Bls12381G1PublicKey>>>()?;
+                Some(CryptoUtils::bls12381_v1_fast_aggregate_verify(hash, keys, *signature))
+            }
+            OracleSignature::Ed25519(signatures) => {
+                if signatures.len() != keys.len() {
+                    return Some(false);
+                }
+                let verified = keys.into_iter().zip(signatures.iter()).map(|(key, signature)| match key {
+                    OracleKey::Ed25519(key) => Some(verify_ed25519(&hash, &key, signature)),
+                    _ => None,
+                }).collect::<Option<Vec<bool>>>()?;
+                Some(verified.into_iter().all(|verified| verified))
+            }
+            OracleSignature::Secp256k1(signatures) => {
+                if signatures.len() != keys.len() {
+                    return Some(false);
+                }
+                let verified = keys.into_iter().zip(signatures.iter()).map(|(key, signature)| match key {
+                    OracleKey::Secp256k1(key) => Some(verify_secp256k1(&hash, &key, signature)),
+                    _ => None,
+                }).collect::<Option<Vec<bool>>>()?;
+                Some(verified.into_iter().all(|verified| verified))
+            }
+        }
+    }
+}
+
+fn verify_ed25519(hash: &[u8], key: &Ed25519PublicKey, signature: &Ed25519Signature) -> bool {
+    DalekVerifyingKey::from_bytes(&key.0)
+        .map(|key| key.verify_strict(hash, &DalekSignature::from_bytes(&signature.0)).is_ok())
+        .unwrap_or(false)
+}
+
+/// Secp256k1 signatures are prefixed with the recovery id, which is not needed for verification
+fn verify_secp256k1(hash: &[u8], key: &Secp256k1PublicKey, signature: &Secp256k1Signature) -> bool {
+    match (K256VerifyingKey::from_sec1_bytes(&key.0), K256Signature::from_slice(&signature.0[1..])) {
+        (Ok(key), Ok(signature)) => key.verify_prehash(hash, &signature).is_ok(),
+        _ => false,
+    }
+}
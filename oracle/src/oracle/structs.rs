@@ -1,4 +1,6 @@
 use scrypto::prelude::*;
+use common::{PairId, ListIndex};
+use super::signatures::OracleKey;
 
 #[derive(ScryptoSbor, Clone, Debug)]
 pub struct Price {
@@ -6,3 +8,183 @@ pub struct Price {
     pub quote: Decimal,
     pub timestamp: Instant,
 }
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PriceV2 {
+    pub pair: String,
+    pub quote: Decimal,
+    pub confidence: Decimal,
+    pub timestamp: Instant,
+}
+
+impl From<Price> for PriceV2 {
+    fn from(price: Price) -> Self {
+        Self {
+            pair: price.pair,
+            quote: price.quote,
+            confidence: dec!(0),
+            timestamp: price.timestamp,
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub enum VersionedPrices {
+    V1(Vec<Price>),
+    V2(Vec<PriceV2>),
+}
+
+impl VersionedPrices {
+    pub fn into_latest(self) -> Vec<PriceV2> {
+        match self {
+            VersionedPrices::V1(prices) => prices.into_iter().map(PriceV2::from).collect(),
+            VersionedPrices::V2(prices) => prices,
+        }
+    }
+}
+
+/// First byte of a compact price payload, SBOR encoded payloads always start with 0x5c
+pub const COMPACT_PRICES_PREFIX: u8 = 0x01;
+/// Number of decimals of the scaled integer quotes in a compact price payload
+pub const COMPACT_QUOTE_DECIMALS: u8 = 8;
+
+/// Price of a compact payload, encoded as a big endian u16 pair index, a u16 timestamp delta from the
+/// payload base timestamp and a u64 quote scaled by 10^COMPACT_QUOTE_DECIMALS
+#[derive(Clone, Debug)]
+pub struct CompactPrice {
+    pub index: u16,
+    pub timestamp: Instant,
+    pub quote: Decimal,
+}
+
+impl CompactPrice {
+    const LENGTH: usize = 12;
+
+    /// Decodes a compact payload made of the prefix, a big endian i64 base timestamp and the encoded prices
+    pub fn decode_all(data: &[u8]) -> Option<Vec<CompactPrice>> {
+        let (prefix, data) = data.split_first()?;
+        if *prefix != COMPACT_PRICES_PREFIX || data.len() < 8 || (data.len() - 8) % Self::LENGTH != 0 {
+            return None;
+        }
+
+        let (base_timestamp, data) = data.split_at(8);
+        let base_timestamp = i64::from_be_bytes(base_timestamp.try_into().ok()?);
+        let scale = Decimal::from(10u64.pow(COMPACT_QUOTE_DECIMALS as u32));
+        data.chunks(Self::LENGTH).map(|chunk| {
+            let index = u16::from_be_bytes(chunk[0..2].try_into().ok()?);
+            let timestamp_delta = u16::from_be_bytes(chunk[2..4].try_into().ok()?);
+            let quote = u64::from_be_bytes(chunk[4..12].try_into().ok()?);
+            Some(CompactPrice {
+                index,
+                timestamp: Instant::new(base_timestamp.checked_add(timestamp_delta as i64)?),
+                quote: Decimal::from(quote) / scale,
+            })
+        }).collect()
+    }
+
+    pub fn into_price(self, pair: String) -> PriceV2 {
+        PriceV2 {
+            pair,
+            quote: self.quote,
+            confidence: dec!(0),
+            timestamp: self.timestamp,
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct OraclePrice {
+    pub quote: Decimal,
+    pub timestamp: Instant,
+    pub confidence: Decimal,
+}
+
+/// Number of recent prices kept per pair in the price history
+pub const PRICE_HISTORY_LENGTH: u64 = 32;
+
+/// Accepted price of a pair, stored in a slot of the pair's price history ring
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PriceHistoryEntry {
+    pub quote: Decimal,
+    pub confidence: Decimal,
+    pub timestamp: Instant,
+}
+
+impl PriceHistoryEntry {
+    pub fn bid(&self) -> Decimal {
+        self.quote - self.confidence
+    }
+
+    pub fn ask(&self) -> Decimal {
+        self.quote + self.confidence
+    }
+}
+
+/// Highest and lowest bid and ask prices of a pair over a time range
+#[derive(ScryptoSbor, Clone, Copy, Debug)]
+pub struct HighLow {
+    pub bid_high: Decimal,
+    pub bid_low: Decimal,
+    pub ask_high: Decimal,
+    pub ask_low: Decimal,
+}
+
+impl HighLow {
+    /// Highest and lowest prices of the entries between the timestamps, inclusive
+    pub fn from_entries(entries: &[PriceHistoryEntry], from: Instant, to: Instant) -> Option<Self> {
+        entries.iter()
+            .filter(|entry| entry.timestamp.compare(from, TimeComparisonOperator::Gte) && entry.timestamp.compare(to, TimeComparisonOperator::Lte))
+            .fold(None, |high_low, entry| match high_low {
+                Some(high_low) => Some(Self {
+                    bid_high: high_low.bid_high.max(entry.bid()),
+                    bid_low: high_low.bid_low.min(entry.bid()),
+                    ask_high: high_low.ask_high.max(entry.ask()),
+                    ask_low: high_low.ask_low.min(entry.ask()),
+                }),
+                None => Some(Self {
+                    bid_high: entry.bid(),
+                    bid_low: entry.bid(),
+                    ask_high: entry.ask(),
+                    ask_low: entry.ask(),
+                }),
+            })
+    }
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct QuarantinedPrice {
+    pub quote: Decimal,
+    pub timestamp: Instant,
+    pub key_ids: Vec<ListIndex>,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct OracleKeyEntry {
+    pub key: OracleKey,
+    /// Key is valid at and after this time, if set
+    pub valid_from: Option<Instant>,
+    /// Key is valid before this time, if set
+    pub valid_until: Option<Instant>,
+    /// Pairs the key is allowed to sign prices for, all pairs if not set
+    pub pairs: Option<HashSet<PairId>>,
+}
+
+impl OracleKeyEntry {
+    pub fn new(key: OracleKey) -> Self {
+        Self {
+            key,
+            valid_from: None,
+            valid_until: None,
+            pairs: None,
+        }
+    }
+
+    pub fn is_valid_at(&self, time: Instant) -> bool {
+        self.valid_from.map_or(true, |valid_from| time.compare(valid_from, TimeComparisonOperator::Gte)) &&
+        self.valid_until.map_or(true, |valid_until| time.compare(valid_until, TimeComparisonOperator::Lt))
+    }
+
+    pub fn is_allowed_pair(&self, pair_id: &PairId) -> bool {
+        self.pairs.as_ref().map_or(true, |pairs| pairs.contains(pair_id))
+    }
+}
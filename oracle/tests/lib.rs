@@ -1,152 +1,338 @@
-// use radix_engine_interface::prelude::*;
-// use scrypto_test::prelude::*;
-// use scrypto_unit::*;
-// use common::PairId;
-
-// #[derive(ScryptoSbor, Debug)]
-// struct Price {
-//     pair: String,
-//     quote: Decimal,
-//     timestamp: i64,
-// }
-
-// #[test]
-// fn test_example() {
-//     let mut test_runner = TestRunnerBuilder::new().without_trace().build();
-
-//     // Create accounts
-//     let (public_key, _private_key, _account_component) = test_runner.new_allocated_account();
-//     let oracle_package = test_runner.compile_and_publish(".");
-
-//     let oracle_private_key = Bls12381G1PrivateKey::from_u64(1).unwrap();
-//     let oracle_public_key = oracle_private_key.public_key();
-
-//     let manifest = ManifestBuilder::new()
-//         .call_function(
-//             oracle_package,
-//             "Oracle",
-//             "new",
-//             manifest_args!(OwnerRole::None, oracle_public_key))
-//         .build();
-//     let receipt = test_runner.execute_manifest_ignoring_fee(
-//         manifest,
-//         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-//     );
-//     let oracle_component = receipt
-//         .expect_commit_success()
-//         .new_component_addresses()[0];
-
-//     let price = Price {
-//         pair: "BTC/USD".to_string(),
-//         quote: dec!(64449.495),
-//         timestamp: 1714099255,
-//     };
-//     let prices = vec![price];
-//     let data = scrypto_encode(&prices).unwrap();
-
-//     let manifest = ManifestBuilder::new()
-//         .call_method(
-//             oracle_component,
-//             "hash",
-//             manifest_args!(data.clone()))
-//         .build();
-//     let receipt = test_runner.execute_manifest_ignoring_fee(
-//         manifest,
-//         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-//     );
-//     let hash: Vec<u8> = receipt.expect_commit_success().output(1);
-
-//     let signature = oracle_private_key.sign_v1(hash.as_slice());
-
-//     let manifest = ManifestBuilder::new()
-//         .call_method(
-//             oracle_component,
-//             "push_prices",
-//             manifest_args!(vec!["BTC/USD"], 0i64, data, signature))
-//         .build();
-//     let receipt = test_runner.execute_manifest_ignoring_fee(
-//         manifest,
-//         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-//     );
-//     let prices: HashMap<PairId, Decimal> = receipt.expect_commit_success().output(1);
-//     println!("{:?}", prices);
-// }
-
-// #[test]
-// fn test_api() {
-//     let mut test_runner = TestRunnerBuilder::new().build();
-
-//     let oracle_public_key = "b9dca0b122bc34356550c32beb31c726f993fcf1fb16aecdbe95b5181e8505b98c5f1286969664d69c4358dc16261640";
-//     let signature = "aa9ff53b151b210f42c4b681b14e1d0081c045c69117d621ff07ebf6f8d8ba116dd553638fa66f4879c165d0d53154e30454d6120da74ae2c16ef186a9dcc1d1ec6d1abc0e00b3b7d2c5fe8637b585b45c38711398dbdf10c88b3d4238d9efc6";
-//     let _hash = "189e2cddc616bd9f0c8529aa23343cd34a796a6dc86608094227e7b504bed881";
-//     let data = "5c202101030c074254432f555344a000a46cb5d1238a732b0c0000000000000000000000000000057a1e326600000000";
-
-//     // Create accounts
-//     let (public_key, _private_key, _account_component) = test_runner.new_allocated_account();
-//     let oracle_package = test_runner.compile_and_publish(".");
-
-//     let oracle_public_key = Bls12381G1PublicKey::try_from(hex::decode(oracle_public_key).unwrap().as_slice()).unwrap();
-
-//     let manifest = ManifestBuilder::new()
-//         .call_function(
-//             oracle_package,
-//             "Oracle",
-//             "new",
-//             manifest_args!(OwnerRole::None, oracle_public_key))
-//         .build();
-//     let receipt = test_runner.execute_manifest_ignoring_fee(
-//         manifest,
-//         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-//     );
-//     let oracle_component = receipt
-//         .expect_commit_success()
-//         .new_component_addresses()[0];
-
-//     let data = hex::decode(data).unwrap();
-
-//     let manifest = ManifestBuilder::new()
-//         .call_method(
-//             oracle_component,
-//             "hash",
-//             manifest_args!(data.clone()))
-//         .build();
-//     let receipt = test_runner.execute_manifest_ignoring_fee(
-//         manifest,
-//         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-//     );
-//     let hash: Vec<u8> = receipt.expect_commit_success().output(1);
-//     assert!(hex::encode(hash) == _hash);
-
-//     let signature = Bls12381G2Signature::try_from(hex::decode(signature).unwrap().as_slice()).unwrap();
-
-//     let manifest = ManifestBuilder::new()
-//         .call_method(
-//             oracle_component,
-//             "push_prices",
-//             manifest_args!(vec!["BTC/USD"], 0i64, data, signature))
-//         .build();
-//     let receipt = test_runner.execute_manifest_ignoring_fee(
-//         manifest,
-//         vec![NonFungibleGlobalId::from_public_key(&public_key)],
-//     );
-//     let prices: HashMap<PairId, Decimal> = receipt.expect_commit_success().output(1);
-//     println!("{:?}", prices);
-// }
-
-// // ------------------------------------------------------Fee Summary-------------------------------------------------------
-// // Execution Cost Units Consumed           :                   6050691
-// // Finalization Cost Units Consumed        :                    305050
-// // Execution Cost in XRD                   :                0.30253455
-// // Finalization Cost in XRD                :                 0.0152525
-// // Tipping Cost in XRD                     :                         0
-// // Storage Cost in XRD                     :             0.03328323307
-// // Royalty Costs in XRD                    :                         0
-
-// // ------------------------------------------------------Fee Summary-------------------------------------------------------
-// // Execution Cost Units Consumed           :                   6111839
-// // Finalization Cost Units Consumed        :                    305061
-// // Execution Cost in XRD                   :                0.30559195
-// // Finalization Cost in XRD                :                0.01525305
-// // Tipping Cost in XRD                     :                         0
-// // Storage Cost in XRD                     :             0.03738403256
-// // Royalty Costs in XRD                    :                         0
+use scrypto_test::prelude::*;
+use common::{PairId, ListIndex};
+use oracle::{
+    PRICE_HISTORY_LENGTH, OracleKey, OracleSignature, Price, PriceV2, VersionedPrices, COMPACT_PRICES_PREFIX,
+    ERROR_INSUFFICIENT_SIGNERS, ERROR_INVALID_SIGNATURE, ERROR_KEY_NOT_VALID, ERROR_KEY_PAIR_NOT_ALLOWED,
+    ERROR_SIGNATURE_SCHEME_MISMATCH, ERROR_MISSING_PAIR_INDEX, ERROR_DUPLICATE_KEY_ID,
+};
+
+const BTC: &str = "BTC/USD";
+const ETH: &str = "ETH/USD";
+
+struct OracleTest {
+    ledger: LedgerSimulator<NoExtension, InMemorySubstateDatabase>,
+    oracle_component: ComponentAddress,
+    round: u64,
+}
+
+impl OracleTest {
+    fn new(keys: HashMap<ListIndex, OracleKey>) -> Self {
+        let mut ledger = LedgerSimulatorBuilder::new().without_kernel_trace().build();
+        let oracle_package = ledger.compile_and_publish(this_package!());
+        let oracle_component = ledger.call_function(
+            oracle_package,
+            "Oracle",
+            "new",
+            manifest_args!(OwnerRole::Fixed(rule!(allow_all)), keys)
+        ).expect_commit_success().new_component_addresses()[0];
+
+        Self {
+            ledger,
+            oracle_component,
+            round: 0,
+        }
+    }
+
+    fn ledger_time(&mut self) -> Instant {
+        self.ledger.get_current_time(TimePrecisionV2::Second)
+    }
+
+    fn increment_ledger_time(&mut self, seconds: i64) -> Instant {
+        let new_time = self.ledger_time().add_seconds(seconds).unwrap();
+        self.round += 1;
+        self.ledger.advance_to_round_at_timestamp(Round::of(self.round), new_time.seconds_since_unix_epoch * 1000);
+        new_time
+    }
+
+    fn call(&mut self, method: &str, args: ManifestArgs) -> TransactionReceiptV1 {
+        self.ledger.call_method(self.oracle_component, method, args)
+    }
+
+    fn push_and_get_prices(
+        &mut self,
+        pair_ids: HashSet<PairId>,
+        data: Vec<u8>,
+        signature: OracleSignature,
+        key_ids: Vec<ListIndex>,
+    ) -> TransactionReceiptV1 {
+        self.call("push_and_get_prices", manifest_args!(pair_ids, data, signature, key_ids))
+    }
+
+    fn prices(&mut self, pair_ids: HashSet<PairId>) -> HashMap<PairId, (Decimal, Instant)> {
+        self.call("get_prices", manifest_args!(pair_ids)).expect_commit_success().output(1)
+    }
+
+    fn price_at(&mut self, pair_id: &str, timestamp: Instant) -> Option<(Decimal, Instant)> {
+        self.call("get_price_at", manifest_args!(pair_id.to_string(), timestamp)).expect_commit_success().output(1)
+    }
+
+    fn high_low(&mut self, pair_id: &str, from: Instant, to: Instant) -> Option<(Decimal, Decimal)> {
+        self.call("get_high_low", manifest_args!(pair_id.to_string(), from, to)).expect_commit_success().output(1)
+    }
+}
+
+fn bls_key(seed: u64) -> OracleKey {
+    OracleKey::Bls12381(Bls12381G1PrivateKey::from_u64(seed).unwrap().public_key())
+}
+
+fn bls_sign(seeds: Vec<u64>, data: &[u8]) -> OracleSignature {
+    let hash = keccak256_hash(data).to_vec();
+    let signatures: Vec<Bls12381G2Signature> = seeds.iter()
+        .map(|seed| Bls12381G1PrivateKey::from_u64(*seed).unwrap().sign_v1(&hash))
+        .collect();
+    OracleSignature::Bls12381(Bls12381G2Signature::aggregate(&signatures, true).unwrap())
+}
+
+fn encode_prices(pair_id: &str, quote: Decimal, timestamp: Instant) -> Vec<u8> {
+    scrypto_encode(&vec![
+        Price {
+            pair: pair_id.to_string(),
+            quote,
+            timestamp,
+        },
+    ]).unwrap()
+}
+
+fn check_error_msg(err: &RuntimeError, expected: &str) -> bool {
+    match err {
+        RuntimeError::ApplicationError(ApplicationError::PanicMessage(msg)) => {
+            msg.contains(expected)
+        },
+        _ => false,
+    }
+}
+
+#[test]
+fn test_push_prices_bls12381() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1), 1 as ListIndex => bls_key(2)));
+
+    let time_1 = test.increment_ledger_time(1);
+    let data_1 = encode_prices(BTC, dec!(60000), time_1);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![2], &data_1), vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    let prices: HashMap<PairId, (Decimal, Instant)> = test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![1], &data_1), vec![0])
+        .expect_commit_success().output(1);
+    assert_eq!(prices[BTC], (dec!(60000), time_1));
+
+    let time_2 = test.increment_ledger_time(1);
+    let data_2 = scrypto_encode(&VersionedPrices::V2(vec![
+        PriceV2 {
+            pair: BTC.to_string(),
+            quote: dec!(61000),
+            confidence: dec!(10),
+            timestamp: time_2,
+        },
+    ])).unwrap();
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_2.clone(), bls_sign(vec![1, 2], &data_2), vec![0, 1])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(61000), time_2));
+}
+
+#[test]
+fn test_push_prices_ed25519() {
+    let ed25519_key = Ed25519PrivateKey::from_u64(2).unwrap();
+    let mut test = OracleTest::new(hashmap!(
+        0 as ListIndex => bls_key(1),
+        1 as ListIndex => OracleKey::Ed25519(ed25519_key.public_key())
+    ));
+
+    let time_1 = test.increment_ledger_time(1);
+    let data_1 = encode_prices(BTC, dec!(60000), time_1);
+    let hash_1 = keccak256_hash(&data_1);
+    let signature_1 = OracleSignature::Ed25519(vec![ed25519_key.sign(&hash_1)]);
+    let signature_invalid = OracleSignature::Ed25519(vec![Ed25519PrivateKey::from_u64(3).unwrap().sign(&hash_1)]);
+
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), signature_1.clone(), vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_SIGNATURE_SCHEME_MISMATCH));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), signature_invalid, vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), OracleSignature::Ed25519(vec![]), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1, signature_1, vec![1])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(60000), time_1));
+}
+
+#[test]
+fn test_push_prices_secp256k1() {
+    let secp256k1_key = Secp256k1PrivateKey::from_u64(2).unwrap();
+    let mut test = OracleTest::new(hashmap!(1 as ListIndex => OracleKey::Secp256k1(secp256k1_key.public_key())));
+
+    let time_1 = test.increment_ledger_time(1);
+    let data_1 = encode_prices(BTC, dec!(60000), time_1);
+    let hash_1 = keccak256_hash(&data_1);
+    let signature_1 = secp256k1_key.sign(&hash_1);
+    let signature_invalid = Secp256k1PrivateKey::from_u64(3).unwrap().sign(&hash_1);
+    let mut signature_tampered = signature_1.clone();
+    signature_tampered.0[64] ^= 1;
+
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), OracleSignature::Secp256k1(vec![signature_invalid]), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), OracleSignature::Secp256k1(vec![signature_tampered]), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INVALID_SIGNATURE));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![2], &data_1), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_SIGNATURE_SCHEME_MISMATCH));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1, OracleSignature::Secp256k1(vec![signature_1]), vec![1])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(60000), time_1));
+
+    // Leading recovery id is not part of the verified signature
+    let time_2 = test.increment_ledger_time(1);
+    let data_2 = encode_prices(BTC, dec!(61000), time_2);
+    let mut signature_2 = secp256k1_key.sign(&keccak256_hash(&data_2));
+    signature_2.0[0] ^= 1;
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_2, OracleSignature::Secp256k1(vec![signature_2]), vec![1])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(61000), time_2));
+}
+
+#[test]
+fn test_push_prices_threshold() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1), 1 as ListIndex => bls_key(2)));
+    test.call("update_threshold", manifest_args!(2u64)).expect_commit_success();
+
+    let time_1 = test.increment_ledger_time(1);
+    let data_1 = encode_prices(BTC, dec!(60000), time_1);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![1], &data_1), vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_INSUFFICIENT_SIGNERS));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![1, 1], &data_1), vec![0, 0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_DUPLICATE_KEY_ID));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![1, 2], &data_1), vec![0, 1])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(60000), time_1));
+}
+
+#[test]
+fn test_push_prices_quarantine() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1), 1 as ListIndex => bls_key(2)));
+    test.call("update_deviation_rate", manifest_args!(BTC.to_string(), Some(dec!(0.001)))).expect_commit_success();
+
+    let time_1 = test.increment_ledger_time(1);
+    let data_1 = encode_prices(BTC, dec!(60000), time_1);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![1], &data_1), vec![0])
+        .expect_commit_success();
+
+    let time_2 = test.increment_ledger_time(1);
+    let data_2 = encode_prices(BTC, dec!(66000), time_2);
+    let prices: HashMap<PairId, (Decimal, Instant)> = test.push_and_get_prices(hashset!(BTC.to_string()), data_2.clone(), bls_sign(vec![1], &data_2), vec![0])
+        .expect_commit_success().output(1);
+    assert_eq!(prices[BTC], (dec!(60000), time_1));
+
+    let time_3 = test.increment_ledger_time(1);
+    let data_3 = encode_prices(BTC, dec!(66010), time_3);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_3.clone(), bls_sign(vec![1], &data_3), vec![0])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(60000), time_1));
+
+    let time_4 = test.increment_ledger_time(1);
+    let data_4 = encode_prices(BTC, dec!(66020), time_4);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_4.clone(), bls_sign(vec![2], &data_4), vec![1])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(66020), time_4));
+}
+
+#[test]
+fn test_push_prices_compact() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1)));
+
+    let time_1 = test.increment_ledger_time(1);
+    let mut data_1 = vec![COMPACT_PRICES_PREFIX];
+    data_1.extend_from_slice(&time_1.seconds_since_unix_epoch.to_be_bytes());
+    data_1.extend_from_slice(&0u16.to_be_bytes());
+    data_1.extend_from_slice(&0u16.to_be_bytes());
+    data_1.extend_from_slice(&6000012345678u64.to_be_bytes());
+    data_1.extend_from_slice(&1u16.to_be_bytes());
+    data_1.extend_from_slice(&2u16.to_be_bytes());
+    data_1.extend_from_slice(&300050000000u64.to_be_bytes());
+
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![1], &data_1), vec![0])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_MISSING_PAIR_INDEX));
+
+    test.call("update_pair_index", manifest_args!(0u16, Some(BTC.to_string()))).expect_commit_success();
+    test.call("update_pair_index", manifest_args!(1u16, Some(ETH.to_string()))).expect_commit_success();
+    test.push_and_get_prices(hashset!(BTC.to_string(), ETH.to_string()), data_1.clone(), bls_sign(vec![1], &data_1), vec![0])
+        .expect_commit_success();
+
+    let prices = test.prices(hashset!(BTC.to_string(), ETH.to_string()));
+    assert_eq!(prices[BTC], (dec!(60000.12345678), time_1));
+    assert_eq!(prices[ETH], (dec!(3000.5), time_1.add_seconds(2).unwrap()));
+}
+
+#[test]
+fn test_push_prices_key_validity() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1), 1 as ListIndex => bls_key(2)));
+
+    let valid_until = test.ledger_time().add_seconds(10).unwrap();
+    test.call("update_key_validity", manifest_args!(1 as ListIndex, None::<Instant>, Some(valid_until), Some(hashset!(BTC.to_string()))))
+        .expect_commit_success();
+
+    let time_1 = test.increment_ledger_time(1);
+    let data_1 = encode_prices(BTC, dec!(60000), time_1);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_1.clone(), bls_sign(vec![2], &data_1), vec![1])
+        .expect_commit_success();
+
+    let data_eth_1 = encode_prices(ETH, dec!(3000), time_1);
+    test.push_and_get_prices(hashset!(ETH.to_string()), data_eth_1.clone(), bls_sign(vec![2], &data_eth_1), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_KEY_PAIR_NOT_ALLOWED));
+    test.push_and_get_prices(hashset!(ETH.to_string()), data_eth_1.clone(), bls_sign(vec![1], &data_eth_1), vec![0])
+        .expect_commit_success();
+
+    let time_2 = test.increment_ledger_time(10);
+    let data_2 = encode_prices(BTC, dec!(61000), time_2);
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_2.clone(), bls_sign(vec![2], &data_2), vec![1])
+        .expect_specific_failure(|err| check_error_msg(err, ERROR_KEY_NOT_VALID));
+    test.push_and_get_prices(hashset!(BTC.to_string()), data_2.clone(), bls_sign(vec![1], &data_2), vec![0])
+        .expect_commit_success();
+    assert_eq!(test.prices(hashset!(BTC.to_string()))[BTC], (dec!(61000), time_2));
+}
+
+#[test]
+fn test_price_history_wraparound() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1)));
+
+    let pushes = PRICE_HISTORY_LENGTH + 8;
+    let mut history: Vec<(Decimal, Instant)> = vec![];
+    for i in 0..pushes {
+        let time = test.increment_ledger_time(1);
+        let quote = if i % 2 == 0 { dec!(60000) + Decimal::from(i * 10) } else { dec!(60000) - Decimal::from(i * 10) };
+        let data = encode_prices(BTC, quote, time);
+        test.push_and_get_prices(hashset!(BTC.to_string()), data.clone(), bls_sign(vec![1], &data), vec![0])
+            .expect_commit_success();
+        history.push((quote, time));
+    }
+
+    let kept = &history[8..];
+    assert_eq!(test.price_at(BTC, history[7].1), None);
+    assert_eq!(test.price_at(BTC, kept[0].1), Some(kept[0]));
+    assert_eq!(test.price_at(BTC, kept[20].1), Some(kept[20]));
+    assert_eq!(test.price_at(BTC, kept[kept.len() - 1].1.add_seconds(100).unwrap()), Some(kept[kept.len() - 1]));
+
+    let high = kept.iter().map(|(quote, _)| *quote).max().unwrap();
+    let low = kept.iter().map(|(quote, _)| *quote).min().unwrap();
+    assert_eq!(test.high_low(BTC, history[0].1, history[history.len() - 1].1), Some((high, low)));
+    assert_eq!(test.high_low(BTC, history[0].1, history[7].1), None);
+}
+
+#[test]
+fn test_price_history_range() {
+    let mut test = OracleTest::new(hashmap!(0 as ListIndex => bls_key(1)));
+
+    let mut history: Vec<(Decimal, Instant)> = vec![];
+    for quote in [dec!(60000), dec!(62000), dec!(59000), dec!(61000)] {
+        let time = test.increment_ledger_time(10);
+        let data = encode_prices(BTC, quote, time);
+        test.push_and_get_prices(hashset!(BTC.to_string()), data.clone(), bls_sign(vec![1], &data), vec![0])
+            .expect_commit_success();
+        history.push((quote, time));
+    }
+
+    assert_eq!(test.price_at(BTC, history[0].1.add_seconds(-1).unwrap()), None);
+    assert_eq!(test.price_at(BTC, history[1].1), Some(history[1]));
+    assert_eq!(test.price_at(BTC, history[1].1.add_seconds(5).unwrap()), Some(history[1]));
+    assert_eq!(test.price_at(ETH, history[1].1), None);
+
+    assert_eq!(test.high_low(BTC, history[0].1, history[3].1), Some((dec!(62000), dec!(59000))));
+    assert_eq!(test.high_low(BTC, history[2].1, history[3].1), Some((dec!(61000), dec!(59000))));
+    assert_eq!(test.high_low(BTC, history[1].1.add_seconds(1).unwrap(), history[2].1.add_seconds(-1).unwrap()), None);
+    assert_eq!(test.high_low(BTC, history[3].1, history[0].1), None);
+}
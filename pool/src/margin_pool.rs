@@ -1,17 +1,30 @@
+pub mod errors;
 pub mod structs;
 
 use scrypto::prelude::*;
-use common::{PairId, _AUTHORITY_RESOURCE, _BASE_RESOURCE};
+use common::{PairId, ListIndex, List, _AUTHORITY_RESOURCE, _BASE_RESOURCE, _LP_RESOURCE};
+pub use self::errors::*;
 pub use self::structs::*;
 
 #[blueprint]
 #[types(
     PairId,
     PoolPosition,
+    ListIndex,
+    LpDeposit,
+    LpWithdrawal,
+    LpLock,
+    PairCheckpointKey,
+    PairCheckpoint,
+    i64,
 )]
 pub mod margin_pool_mod {
     const AUTHORITY_RESOURCE: ResourceAddress = _AUTHORITY_RESOURCE;
     const BASE_RESOURCE: ResourceAddress = _BASE_RESOURCE;
+    const LP_RESOURCE: ResourceAddress = _LP_RESOURCE;
+    const CHECKPOINT_PERIOD_SECONDS: i64 = 3600;
+    const CHECKPOINT_RANGE_MAX: i64 = 744;
+    const CHECKPOINT_SLOTS: i64 = 2160;
 
     enable_method_auth! { 
         roles {
@@ -22,34 +35,150 @@ pub mod margin_pool_mod {
             get_info => PUBLIC;
             get_position => PUBLIC;
             get_positions => PUBLIC;
+            get_deposit => PUBLIC;
+            get_deposits => PUBLIC;
+            get_withdrawal => PUBLIC;
+            get_withdrawals => PUBLIC;
+            get_lock => PUBLIC;
+            get_locks => PUBLIC;
+            get_pair_history => PUBLIC;
 
             // Authority protected methods
             update => restrict_to: [authority];
+            remove_position => restrict_to: [authority];
             deposit => restrict_to: [authority];
             withdraw => restrict_to: [authority];
+            queue_deposit => restrict_to: [authority];
+            cancel_deposit => restrict_to: [authority];
+            fill_deposit => restrict_to: [authority];
+            claim_deposit => restrict_to: [authority];
+            queue_withdrawal => restrict_to: [authority];
+            cancel_withdrawal => restrict_to: [authority];
+            fill_withdrawal => restrict_to: [authority];
+            claim_withdrawal => restrict_to: [authority];
+            lock => restrict_to: [authority];
+            claim_lock_rewards => restrict_to: [authority];
+            unlock => restrict_to: [authority];
         }
     }
 
     struct MarginPool {
         positions: KeyValueStore<PairId, PoolPosition>,
+        checkpoints: KeyValueStore<PairCheckpointKey, PairCheckpoint>,
+        checkpoint_starts: KeyValueStore<PairId, i64>,
         base_tokens: Vault,
         virtual_balance: Decimal,
         unrealized_pool_funding: Decimal,
         skew_abs_snap: Decimal,
         pnl_snap: Decimal,
+        senior_book: Decimal,
+        rebate_period_start: Instant,
+        rebate_spent: Decimal,
+        deposit_receipt: ResourceManager,
+        deposits: List<LpDeposit>,
+        deposits_start: ListIndex,
+        deposit_base_tokens: Vault,
+        deposit_claims: Vault,
+        withdrawal_receipt: ResourceManager,
+        withdrawals: List<LpWithdrawal>,
+        withdrawals_start: ListIndex,
+        withdrawal_lp_tokens: Vault,
+        withdrawal_claims: Vault,
+        lock_receipt: ResourceManager,
+        locks: List<LpLock>,
+        locked_lp_tokens: Vault,
+        lock_weight: Decimal,
+        lock_reward_index: Decimal,
     }
 
     impl MarginPool {
         pub fn new(owner_role: OwnerRole) -> Global<MarginPool> {
-            let (component_reservation, _this) = Runtime::allocate_component_address(MarginPool::blueprint_id());
+            let (component_reservation, this) = Runtime::allocate_component_address(MarginPool::blueprint_id());
+
+            let deposit_receipt = ResourceBuilder::new_integer_non_fungible::<LpDepositReceipt>(owner_role.clone())
+                .metadata(metadata!(
+                    init {
+                        "package" => GlobalAddress::from(Runtime::package_address()), locked;
+                        "component" => GlobalAddress::from(this), locked;
+                        "name" => format!("LP Deposit Receipt"), updatable;
+                        "description" => format!("Claim on a queued LP deposit."), updatable;
+                    }
+                ))
+                .mint_roles(mint_roles! {
+                    minter => rule!(require(global_caller(this)));
+                    minter_updater => rule!(deny_all);
+                })
+                .burn_roles(burn_roles! {
+                    burner => rule!(require(global_caller(this)));
+                    burner_updater => rule!(deny_all);
+                })
+                .create_with_no_initial_supply();
+
+            let withdrawal_receipt = ResourceBuilder::new_integer_non_fungible::<LpWithdrawalReceipt>(owner_role.clone())
+                .metadata(metadata!(
+                    init {
+                        "package" => GlobalAddress::from(Runtime::package_address()), locked;
+                        "component" => GlobalAddress::from(this), locked;
+                        "name" => format!("LP Withdrawal Receipt"), updatable;
+                        "description" => format!("Claim on a queued LP withdrawal."), updatable;
+                    }
+                ))
+                .mint_roles(mint_roles! {
+                    minter => rule!(require(global_caller(this)));
+                    minter_updater => rule!(deny_all);
+                })
+                .burn_roles(burn_roles! {
+                    burner => rule!(require(global_caller(this)));
+                    burner_updater => rule!(deny_all);
+                })
+                .create_with_no_initial_supply();
+
+            let lock_receipt = ResourceBuilder::new_integer_non_fungible::<LpLockReceipt>(owner_role.clone())
+                .metadata(metadata!(
+                    init {
+                        "package" => GlobalAddress::from(Runtime::package_address()), locked;
+                        "component" => GlobalAddress::from(this), locked;
+                        "name" => format!("LP Lock Receipt"), updatable;
+                        "description" => format!("Claim on time-locked LP tokens and their boosted fee share."), updatable;
+                    }
+                ))
+                .mint_roles(mint_roles! {
+                    minter => rule!(require(global_caller(this)));
+                    minter_updater => rule!(deny_all);
+                })
+                .burn_roles(burn_roles! {
+                    burner => rule!(require(global_caller(this)));
+                    burner_updater => rule!(deny_all);
+                })
+                .create_with_no_initial_supply();
 
             Self {
                 positions: KeyValueStore::new_with_registered_type(),
+                checkpoints: KeyValueStore::new_with_registered_type(),
+                checkpoint_starts: KeyValueStore::new_with_registered_type(),
                 base_tokens: Vault::new(BASE_RESOURCE),
                 virtual_balance: dec!(0),
                 unrealized_pool_funding: dec!(0),
                 skew_abs_snap: dec!(0),
                 pnl_snap: dec!(0),
+                senior_book: dec!(0),
+                rebate_period_start: Clock::current_time_rounded_to_seconds(),
+                rebate_spent: dec!(0),
+                deposit_receipt,
+                deposits: List::new(MarginPoolKeyValueStore::new_with_registered_type),
+                deposits_start: 0,
+                deposit_base_tokens: Vault::new(BASE_RESOURCE),
+                deposit_claims: Vault::new(LP_RESOURCE),
+                withdrawal_receipt,
+                withdrawals: List::new(MarginPoolKeyValueStore::new_with_registered_type),
+                withdrawals_start: 0,
+                withdrawal_lp_tokens: Vault::new(LP_RESOURCE),
+                withdrawal_claims: Vault::new(BASE_RESOURCE),
+                lock_receipt,
+                locks: List::new(MarginPoolKeyValueStore::new_with_registered_type),
+                locked_lp_tokens: Vault::new(LP_RESOURCE),
+                lock_weight: dec!(0),
+                lock_reward_index: dec!(0),
             }
             .instantiate()
             .prepare_to_globalize(owner_role)
@@ -68,6 +197,17 @@ pub mod margin_pool_mod {
                 unrealized_pool_funding: self.unrealized_pool_funding,
                 skew_abs_snap: self.skew_abs_snap,
                 pnl_snap: self.pnl_snap,
+                senior_book: self.senior_book,
+                rebate_period_start: self.rebate_period_start,
+                rebate_spent: self.rebate_spent,
+                deposits_start: self.deposits_start,
+                deposits_len: self.deposits.len(),
+                deposit_receipt_resource: self.deposit_receipt.address(),
+                withdrawals_start: self.withdrawals_start,
+                withdrawals_len: self.withdrawals.len(),
+                withdrawal_receipt_resource: self.withdrawal_receipt.address(),
+                lock_weight: self.lock_weight,
+                lock_receipt_resource: self.lock_receipt.address(),
             }
         }
 
@@ -82,8 +222,64 @@ pub mod margin_pool_mod {
             }).collect()
         }
 
+        pub fn get_deposit(&self, index: ListIndex) -> Option<LpDeposit> {
+            self.deposits.get(index).map(|deposit| deposit.clone())
+        }
+
+        pub fn get_deposits(&self, n: ListIndex, start: Option<ListIndex>) -> Vec<(ListIndex, LpDeposit)> {
+            let start = start.unwrap_or(0);
+            let end = (start + n).min(self.deposits.len());
+            (start..end).zip(self.deposits.range(start, end).into_iter()).collect()
+        }
+
+        pub fn get_withdrawal(&self, index: ListIndex) -> Option<LpWithdrawal> {
+            self.withdrawals.get(index).map(|withdrawal| withdrawal.clone())
+        }
+
+        pub fn get_withdrawals(&self, n: ListIndex, start: Option<ListIndex>) -> Vec<(ListIndex, LpWithdrawal)> {
+            let start = start.unwrap_or(0);
+            let end = (start + n).min(self.withdrawals.len());
+            (start..end).zip(self.withdrawals.range(start, end).into_iter()).collect()
+        }
+
+        pub fn get_lock(&self, index: ListIndex) -> Option<LpLock> {
+            self.locks.get(index).map(|lock| lock.clone())
+        }
+
+        pub fn get_locks(&self, n: ListIndex, start: Option<ListIndex>) -> Vec<(ListIndex, LpLock)> {
+            let start = start.unwrap_or(0);
+            let end = (start + n).min(self.locks.len());
+            (start..end).zip(self.locks.range(start, end).into_iter()).collect()
+        }
+
+        pub fn get_pair_history(&self, pair_id: PairId, start: Instant, end: Instant) -> Vec<PairCheckpoint> {
+            let period_start = start.seconds_since_unix_epoch / CHECKPOINT_PERIOD_SECONDS;
+            let period_end = end.seconds_since_unix_epoch / CHECKPOINT_PERIOD_SECONDS;
+            assert!(
+                period_end - period_start < CHECKPOINT_RANGE_MAX,
+                "{}, VALUE:{}, REQUIRED:{}, OP:< |", ERROR_HISTORY_RANGE_TOO_LONG, period_end - period_start, CHECKPOINT_RANGE_MAX
+            );
+            let period_start = self.checkpoint_starts.get(&pair_id).map(|v| (*v).max(period_start)).unwrap_or(period_start);
+
+            (period_start..=period_end).filter_map(|period| {
+                let checkpoint_key = PairCheckpointKey { 
+                    pair_id: pair_id.clone(), 
+                    slot: period.rem_euclid(CHECKPOINT_SLOTS),
+                };
+                self.checkpoints.get(&checkpoint_key)
+                    .filter(|checkpoint| checkpoint.period == period)
+                    .map(|checkpoint| checkpoint.clone())
+            }).collect()
+        }
+
         pub fn update(&mut self, update: MarginPoolUpdates) {
             for (position_id, position) in update.position_updates {
+                let period = position.last_update.seconds_since_unix_epoch / CHECKPOINT_PERIOD_SECONDS;
+                let checkpoint_key = PairCheckpointKey {
+                    pair_id: position_id.clone(),
+                    slot: period.rem_euclid(CHECKPOINT_SLOTS),
+                };
+                self.checkpoints.insert(checkpoint_key, PairCheckpoint::new(period, &position));
                 self.positions.insert(position_id, position);
             }
 
@@ -91,6 +287,23 @@ pub mod margin_pool_mod {
             self.unrealized_pool_funding = update.unrealized_pool_funding;
             self.skew_abs_snap = update.skew_abs_snap;
             self.pnl_snap = update.pnl_snap;
+            self.senior_book = update.senior_book;
+            self.rebate_period_start = update.rebate_period_start;
+            self.rebate_spent = update.rebate_spent;
+            if update.lock_rewards.is_positive() && self.lock_weight.is_positive() {
+                self.lock_reward_index += update.lock_rewards / self.lock_weight;
+            }
+        }
+
+        pub fn remove_position(&mut self, pair_id: PairId) {
+            let period = Clock::current_time_rounded_to_seconds().seconds_since_unix_epoch / CHECKPOINT_PERIOD_SECONDS;
+            let checkpoint_key = PairCheckpointKey {
+                pair_id: pair_id.clone(),
+                slot: period.rem_euclid(CHECKPOINT_SLOTS),
+            };
+            self.checkpoints.remove(&checkpoint_key);
+            self.checkpoint_starts.insert(pair_id.clone(), period);
+            self.positions.remove(&pair_id);
         }
 
         pub fn deposit(&mut self, token: Bucket) {
@@ -100,5 +313,273 @@ pub mod margin_pool_mod {
         pub fn withdraw(&mut self, amount: Decimal, withdraw_strategy: WithdrawStrategy) -> Bucket {
             self.base_tokens.take_advanced(amount, withdraw_strategy)
         }
+
+        pub fn queue_deposit(&mut self, token: Bucket) -> (Bucket, ListIndex) {
+            let index = self.deposits.len();
+            let amount = token.amount();
+            let submission = Clock::current_time_rounded_to_seconds();
+
+            self.deposit_base_tokens.put(token);
+            self.deposits.push(LpDeposit {
+                amount,
+                lp_amount: dec!(0),
+                submission,
+                status: LiquidityRequestStatus::Queued,
+            });
+            let receipt = self.deposit_receipt.mint_non_fungible(
+                &NonFungibleLocalId::integer(index), 
+                LpDepositReceipt {
+                    amount,
+                    submission,
+                },
+            );
+
+            (receipt, index)
+        }
+
+        pub fn cancel_deposit(&mut self, receipt: Bucket) -> (Bucket, ListIndex) {
+            let index = Self::_burn_receipt(&self.deposit_receipt, receipt);
+            let mut deposit = self.deposits.get_mut(index).expect(ERROR_MISSING_DEPOSIT);
+            assert!(
+                deposit.status == LiquidityRequestStatus::Queued,
+                "{}", ERROR_DEPOSIT_NOT_QUEUED
+            );
+            deposit.status = LiquidityRequestStatus::Cancelled;
+            let amount = deposit.amount;
+            drop(deposit);
+
+            self._advance_deposits_start();
+
+            (self.deposit_base_tokens.take(amount), index)
+        }
+
+        pub fn fill_deposit(&mut self, index: ListIndex, lp_token: Bucket) -> Bucket {
+            assert!(
+                index >= self.deposits_start,
+                "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_DEPOSIT_OUT_OF_ORDER, index, self.deposits_start
+            );
+            let mut deposit = self.deposits.get_mut(index).expect(ERROR_MISSING_DEPOSIT);
+            assert!(
+                deposit.status == LiquidityRequestStatus::Queued,
+                "{}", ERROR_DEPOSIT_NOT_QUEUED
+            );
+            deposit.status = LiquidityRequestStatus::Filled;
+            deposit.lp_amount = lp_token.amount();
+            let amount = deposit.amount;
+            drop(deposit);
+
+            self.deposit_claims.put(lp_token);
+            self._advance_deposits_start();
+
+            self.deposit_base_tokens.take(amount)
+        }
+
+        pub fn claim_deposit(&mut self, receipt: Bucket) -> (Bucket, ListIndex) {
+            let index = Self::_burn_receipt(&self.deposit_receipt, receipt);
+            let mut deposit = self.deposits.get_mut(index).expect(ERROR_MISSING_DEPOSIT);
+            assert!(
+                deposit.status == LiquidityRequestStatus::Filled,
+                "{}", ERROR_DEPOSIT_NOT_FILLED
+            );
+            deposit.status = LiquidityRequestStatus::Claimed;
+            let lp_amount = deposit.lp_amount;
+            drop(deposit);
+
+            (self.deposit_claims.take(lp_amount), index)
+        }
+
+        pub fn queue_withdrawal(&mut self, lp_token: Bucket, priority: i64) -> (Bucket, ListIndex) {
+            let index = self.withdrawals.len();
+            let lp_amount = lp_token.amount();
+            let submission = Clock::current_time_rounded_to_seconds();
+
+            self.withdrawal_lp_tokens.put(lp_token);
+            self.withdrawals.push(LpWithdrawal {
+                lp_amount,
+                amount: dec!(0),
+                submission,
+                priority,
+                status: LiquidityRequestStatus::Queued,
+            });
+            let receipt = self.withdrawal_receipt.mint_non_fungible(
+                &NonFungibleLocalId::integer(index), 
+                LpWithdrawalReceipt {
+                    lp_amount,
+                    submission,
+                },
+            );
+
+            (receipt, index)
+        }
+
+        pub fn cancel_withdrawal(&mut self, receipt: Bucket) -> (Bucket, ListIndex) {
+            let index = Self::_burn_receipt(&self.withdrawal_receipt, receipt);
+            let mut withdrawal = self.withdrawals.get_mut(index).expect(ERROR_MISSING_WITHDRAWAL);
+            assert!(
+                withdrawal.status == LiquidityRequestStatus::Queued,
+                "{}", ERROR_WITHDRAWAL_NOT_QUEUED
+            );
+            withdrawal.status = LiquidityRequestStatus::Cancelled;
+            let lp_amount = withdrawal.lp_amount;
+            drop(withdrawal);
+
+            self._advance_withdrawals_start();
+
+            (self.withdrawal_lp_tokens.take(lp_amount), index)
+        }
+
+        pub fn fill_withdrawal(&mut self, index: ListIndex, token: Bucket) -> Bucket {
+            assert!(
+                index >= self.withdrawals_start,
+                "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_WITHDRAWAL_OUT_OF_ORDER, index, self.withdrawals_start
+            );
+            let mut withdrawal = self.withdrawals.get_mut(index).expect(ERROR_MISSING_WITHDRAWAL);
+            assert!(
+                withdrawal.status == LiquidityRequestStatus::Queued,
+                "{}", ERROR_WITHDRAWAL_NOT_QUEUED
+            );
+            withdrawal.status = LiquidityRequestStatus::Filled;
+            withdrawal.amount = token.amount();
+            let lp_amount = withdrawal.lp_amount;
+            drop(withdrawal);
+
+            self.withdrawal_claims.put(token);
+            self._advance_withdrawals_start();
+
+            self.withdrawal_lp_tokens.take(lp_amount)
+        }
+
+        pub fn claim_withdrawal(&mut self, receipt: Bucket) -> (Bucket, ListIndex) {
+            let index = Self::_burn_receipt(&self.withdrawal_receipt, receipt);
+            let mut withdrawal = self.withdrawals.get_mut(index).expect(ERROR_MISSING_WITHDRAWAL);
+            assert!(
+                withdrawal.status == LiquidityRequestStatus::Filled,
+                "{}", ERROR_WITHDRAWAL_NOT_FILLED
+            );
+            withdrawal.status = LiquidityRequestStatus::Claimed;
+            let amount = withdrawal.amount;
+            drop(withdrawal);
+
+            (self.withdrawal_claims.take(amount), index)
+        }
+
+        pub fn lock(&mut self, lp_token: Bucket, term: i64, boost: Decimal) -> (Bucket, ListIndex) {
+            let index = self.locks.len();
+            let lp_amount = lp_token.amount();
+            let weight = lp_amount * boost;
+            let unlock = Clock::current_time_rounded_to_seconds().add_seconds(term).expect(ERROR_LOCK_TERM_OVERFLOW);
+
+            self.locked_lp_tokens.put(lp_token);
+            self.lock_weight += weight;
+            self.locks.push(LpLock {
+                lp_amount,
+                weight,
+                term,
+                unlock,
+                reward_index: self.lock_reward_index,
+                reward_unpaid: dec!(0),
+                unlocked: false,
+            });
+            let receipt = self.lock_receipt.mint_non_fungible(
+                &NonFungibleLocalId::integer(index), 
+                LpLockReceipt {
+                    lp_amount,
+                    term,
+                    unlock,
+                },
+            );
+
+            (receipt, index)
+        }
+
+        pub fn claim_lock_rewards(&mut self, index: ListIndex, reward_max: Decimal) -> Decimal {
+            let mut lock = self.locks.get_mut(index).expect(ERROR_MISSING_LOCK);
+            let accrued = if lock.unlocked {
+                dec!(0)
+            } else {
+                lock.weight * (self.lock_reward_index - lock.reward_index)
+            };
+            lock.reward_index = self.lock_reward_index;
+            let reward = (accrued + lock.reward_unpaid).min(reward_max);
+            lock.reward_unpaid += accrued - reward;
+
+            reward
+        }
+
+        pub fn unlock(&mut self, receipt: Bucket, reward_max: Decimal) -> (Bucket, Option<Bucket>, Decimal, i64, ListIndex) {
+            let index = Self::_receipt_index(&self.lock_receipt, &receipt);
+            let current_time = Clock::current_time_rounded_to_seconds();
+            let mut lock = self.locks.get_mut(index).expect(ERROR_MISSING_LOCK);
+            assert!(
+                !lock.unlocked || lock.reward_unpaid.is_positive(),
+                "{}", ERROR_LOCK_UNLOCKED
+            );
+            assert!(
+                current_time.compare(lock.unlock, TimeComparisonOperator::Gte),
+                "{}, VALUE:{}, REQUIRED:{}, OP:>= |", ERROR_LOCK_NOT_EXPIRED, current_time.seconds_since_unix_epoch, lock.unlock.seconds_since_unix_epoch
+            );
+            // An unlocked lock only pays out its unpaid remainder
+            let (accrued, lp_amount, weight) = if lock.unlocked {
+                (dec!(0), dec!(0), dec!(0))
+            } else {
+                (lock.weight * (self.lock_reward_index - lock.reward_index), lock.lp_amount, lock.weight)
+            };
+            let reward = (accrued + lock.reward_unpaid).min(reward_max);
+            lock.reward_unpaid += accrued - reward;
+            lock.reward_index = self.lock_reward_index;
+            lock.unlocked = true;
+            let term = lock.term;
+            let reward_unpaid = lock.reward_unpaid;
+            drop(lock);
+
+            self.lock_weight -= weight;
+
+            let receipt = if reward_unpaid.is_zero() {
+                receipt.burn();
+                None
+            } else {
+                Some(receipt)
+            };
+
+            (self.locked_lp_tokens.take(lp_amount), receipt, reward, term, index)
+        }
+
+        fn _advance_deposits_start(&mut self) {
+            while self.deposits_start < self.deposits.len() {
+                let status = self.deposits.get(self.deposits_start).expect(ERROR_MISSING_DEPOSIT).status;
+                if status == LiquidityRequestStatus::Queued {
+                    break;
+                }
+                self.deposits_start += 1;
+            }
+        }
+
+        fn _advance_withdrawals_start(&mut self) {
+            while self.withdrawals_start < self.withdrawals.len() {
+                let status = self.withdrawals.get(self.withdrawals_start).expect(ERROR_MISSING_WITHDRAWAL).status;
+                if status == LiquidityRequestStatus::Queued {
+                    break;
+                }
+                self.withdrawals_start += 1;
+            }
+        }
+
+        fn _burn_receipt(receipt_manager: &ResourceManager, receipt: Bucket) -> ListIndex {
+            let index = Self::_receipt_index(receipt_manager, &receipt);
+            receipt.burn();
+
+            index
+        }
+
+        fn _receipt_index(receipt_manager: &ResourceManager, receipt: &Bucket) -> ListIndex {
+            assert!(
+                receipt.resource_address() == receipt_manager.address() && receipt.amount() == dec!(1),
+                "{}", ERROR_INVALID_RECEIPT
+            );
+            match receipt.as_non_fungible().non_fungible_local_id() {
+                NonFungibleLocalId::Integer(id) => id.value(),
+                _ => panic!("{}", ERROR_INVALID_RECEIPT),
+            }
+        }
     }
-}
\ No newline at end of file
+}
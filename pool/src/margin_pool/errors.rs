@@ -0,0 +1,14 @@
+pub const ERROR_INVALID_RECEIPT: &str = "Invalid receipt";
+pub const ERROR_MISSING_DEPOSIT: &str = "Deposit not found";
+pub const ERROR_DEPOSIT_NOT_QUEUED: &str = "Deposit not queued";
+pub const ERROR_DEPOSIT_NOT_FILLED: &str = "Deposit not filled";
+pub const ERROR_DEPOSIT_OUT_OF_ORDER: &str = "Deposit filled out of order";
+pub const ERROR_MISSING_WITHDRAWAL: &str = "Withdrawal not found";
+pub const ERROR_WITHDRAWAL_NOT_QUEUED: &str = "Withdrawal not queued";
+pub const ERROR_WITHDRAWAL_NOT_FILLED: &str = "Withdrawal not filled";
+pub const ERROR_WITHDRAWAL_OUT_OF_ORDER: &str = "Withdrawal filled out of order";
+pub const ERROR_MISSING_LOCK: &str = "Lock not found";
+pub const ERROR_LOCK_NOT_EXPIRED: &str = "Lock not expired";
+pub const ERROR_LOCK_UNLOCKED: &str = "Lock already unlocked";
+pub const ERROR_LOCK_TERM_OVERFLOW: &str = "Lock term overflow";
+pub const ERROR_HISTORY_RANGE_TOO_LONG: &str = "History range too long";
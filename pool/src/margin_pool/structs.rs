@@ -1,5 +1,5 @@
 use scrypto::prelude::*;
-use common::PairId;
+use common::{PairId, ListIndex};
 
 #[derive(ScryptoSbor, Clone, Debug)]
 pub struct PoolPosition {
@@ -13,6 +13,7 @@ pub struct PoolPosition {
     pub funding_short_index: Decimal,
     pub last_update: Instant,
     pub last_price: Decimal,
+    pub mark_price: Decimal,
 }
 
 impl Default for PoolPosition {
@@ -28,6 +29,40 @@ impl Default for PoolPosition {
             funding_short_index: dec!(0),
             last_update: Clock::current_time_rounded_to_seconds(),
             last_price: dec!(1),
+            mark_price: dec!(0),
+        }
+    }
+}
+
+#[derive(ScryptoSbor, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct PairCheckpointKey {
+    pub pair_id: PairId,
+    pub slot: i64,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct PairCheckpoint {
+    pub period: i64,
+    pub oi_long: Decimal,
+    pub oi_short: Decimal,
+    pub funding_2_rate: Decimal,
+    pub funding_long_index: Decimal,
+    pub funding_short_index: Decimal,
+    pub last_update: Instant,
+    pub last_price: Decimal,
+}
+
+impl PairCheckpoint {
+    pub fn new(period: i64, position: &PoolPosition) -> Self {
+        PairCheckpoint {
+            period,
+            oi_long: position.oi_long,
+            oi_short: position.oi_short,
+            funding_2_rate: position.funding_2_rate,
+            funding_long_index: position.funding_long_index,
+            funding_short_index: position.funding_short_index,
+            last_update: position.last_update,
+            last_price: position.last_price,
         }
     }
 }
@@ -40,6 +75,17 @@ pub struct MarginPoolInfo {
     pub unrealized_pool_funding: Decimal,
     pub skew_abs_snap: Decimal,
     pub pnl_snap: Decimal,
+    pub senior_book: Decimal,
+    pub rebate_period_start: Instant,
+    pub rebate_spent: Decimal,
+    pub deposits_start: ListIndex,
+    pub deposits_len: ListIndex,
+    pub deposit_receipt_resource: ResourceAddress,
+    pub withdrawals_start: ListIndex,
+    pub withdrawals_len: ListIndex,
+    pub withdrawal_receipt_resource: ResourceAddress,
+    pub lock_weight: Decimal,
+    pub lock_receipt_resource: ResourceAddress,
 }
 
 #[derive(ScryptoSbor)]
@@ -49,4 +95,63 @@ pub struct MarginPoolUpdates {
     pub unrealized_pool_funding: Decimal,
     pub skew_abs_snap: Decimal,
     pub pnl_snap: Decimal,
-}
\ No newline at end of file
+    pub senior_book: Decimal,
+    pub rebate_period_start: Instant,
+    pub rebate_spent: Decimal,
+    pub lock_rewards: Decimal,
+}
+
+#[derive(ScryptoSbor, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LiquidityRequestStatus {
+    Queued,
+    Cancelled,
+    Filled,
+    Claimed,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LpDeposit {
+    pub amount: Decimal,
+    pub lp_amount: Decimal,
+    pub submission: Instant,
+    pub status: LiquidityRequestStatus,
+}
+
+#[derive(ScryptoSbor, NonFungibleData, Clone, Debug)]
+pub struct LpDepositReceipt {
+    pub amount: Decimal,
+    pub submission: Instant,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LpWithdrawal {
+    pub lp_amount: Decimal,
+    pub amount: Decimal,
+    pub submission: Instant,
+    pub priority: i64,
+    pub status: LiquidityRequestStatus,
+}
+
+#[derive(ScryptoSbor, NonFungibleData, Clone, Debug)]
+pub struct LpWithdrawalReceipt {
+    pub lp_amount: Decimal,
+    pub submission: Instant,
+}
+
+#[derive(ScryptoSbor, Clone, Debug)]
+pub struct LpLock {
+    pub lp_amount: Decimal,
+    pub weight: Decimal,
+    pub term: i64,
+    pub unlock: Instant,
+    pub reward_index: Decimal,
+    pub reward_unpaid: Decimal,
+    pub unlocked: bool,
+}
+
+#[derive(ScryptoSbor, NonFungibleData, Clone, Debug)]
+pub struct LpLockReceipt {
+    pub lp_amount: Decimal,
+    pub term: i64,
+    pub unlock: Instant,
+}